@@ -17,6 +17,37 @@ fn permute_12_rounds(bencher: divan::Bencher) {
     bencher
         .counter(BytesCount::new(state_byte_len))
         .counter(ItemsCount::new(1usize))
-        .with_inputs(|| (state.clone()))
+        .with_inputs(|| state)
         .bench_values(|mut state| keccak::permute(divan::black_box(&mut state)));
 }
+
+#[cfg(feature = "dev")]
+#[divan::bench]
+fn permute_12_rounds_lane_complemented(bencher: divan::Bencher) {
+    let mut rng = rand::rng();
+
+    let mut state = [0u64; 25];
+    let state_byte_len = state.len() * 8;
+    rng.fill(&mut state);
+
+    bencher
+        .counter(BytesCount::new(state_byte_len))
+        .counter(ItemsCount::new(1usize))
+        .with_inputs(|| state)
+        .bench_values(|mut state| keccak::permute_nr_lane_complemented::<12>(divan::black_box(&mut state)));
+}
+
+#[divan::bench(consts = [2, 4, 8])]
+fn permute_many_12_rounds<const N: usize>(bencher: divan::Bencher) {
+    let mut rng = rand::rng();
+
+    let mut state = [[0u64; N]; 25];
+    let state_byte_len = state.len() * N * 8;
+    state.iter_mut().for_each(|lanes| rng.fill(lanes));
+
+    bencher
+        .counter(BytesCount::new(state_byte_len))
+        .counter(ItemsCount::new(N))
+        .with_inputs(|| state)
+        .bench_values(|mut state| keccak::permute_many::<N>(divan::black_box(&mut state)));
+}
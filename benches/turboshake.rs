@@ -1,7 +1,7 @@
 use divan::counter::{BytesCount, BytesFormat, ItemsCount};
 use rand::prelude::*;
 use std::fmt::Display;
-use turboshake::{TurboShake128, TurboShake256};
+use turboshake::{TurboShake128, TurboShake128Prefix, TurboShake256};
 
 fn main() {
     divan::Divan::default().bytes_format(BytesFormat::Binary).run_benches();
@@ -99,3 +99,85 @@ fn turboshake256(bencher: divan::Bencher, io_size: &InputOutputSize) {
             let _ = hasher.squeeze(divan::black_box(&mut md));
         });
 }
+
+#[divan::bench(args = ARGS)]
+fn turboshake128_oneshot(bencher: divan::Bencher, io_size: &InputOutputSize) {
+    let mut rng = rand::rng();
+
+    let mut msg = vec![0u8; io_size.msg_byte_len];
+    let mut md = vec![0u8; io_size.md_byte_len];
+
+    rng.fill_bytes(&mut msg);
+    rng.fill_bytes(&mut md);
+
+    bencher
+        .counter(BytesCount::new(io_size.msg_byte_len + io_size.md_byte_len))
+        .counter(ItemsCount::new(1usize))
+        .with_inputs(|| (msg.clone(), md.clone()))
+        .bench_values(|(msg, mut md)| {
+            TurboShake128::oneshot::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(divan::black_box(&msg), divan::black_box(&mut md));
+        });
+}
+
+#[divan::bench(args = ARGS)]
+fn turboshake256_oneshot(bencher: divan::Bencher, io_size: &InputOutputSize) {
+    let mut rng = rand::rng();
+
+    let mut msg = vec![0u8; io_size.msg_byte_len];
+    let mut md = vec![0u8; io_size.md_byte_len];
+
+    rng.fill_bytes(&mut msg);
+    rng.fill_bytes(&mut md);
+
+    bencher
+        .counter(BytesCount::new(io_size.msg_byte_len + io_size.md_byte_len))
+        .counter(ItemsCount::new(1usize))
+        .with_inputs(|| (msg.clone(), md.clone()))
+        .bench_values(|(msg, mut md)| {
+            TurboShake256::oneshot::<{ TurboShake256::DEFAULT_DOMAIN_SEPARATOR }>(divan::black_box(&msg), divan::black_box(&mut md));
+        });
+}
+
+#[divan::bench(args = [48, 200])]
+fn turboshake128_reabsorbing_prefix(bencher: divan::Bencher, prefix_byte_len: usize) {
+    let mut rng = rand::rng();
+
+    let mut prefix = vec![0u8; prefix_byte_len];
+    let mut msg = [0u8; 32];
+    let mut md = [0u8; 32];
+
+    rng.fill_bytes(&mut prefix);
+    rng.fill_bytes(&mut msg);
+
+    bencher.counter(ItemsCount::new(1usize)).bench_local(|| {
+        let mut hasher = TurboShake128::default();
+
+        let _ = hasher.absorb(divan::black_box(&prefix));
+        let _ = hasher.absorb(divan::black_box(&msg));
+        let _ = hasher.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>();
+        let _ = hasher.squeeze(divan::black_box(&mut md));
+    });
+}
+
+#[divan::bench(args = [48, 200])]
+fn turboshake128_resetting_to_prefix(bencher: divan::Bencher, prefix_byte_len: usize) {
+    let mut rng = rand::rng();
+
+    let mut prefix = vec![0u8; prefix_byte_len];
+    let mut msg = [0u8; 32];
+    let mut md = [0u8; 32];
+
+    rng.fill_bytes(&mut prefix);
+    rng.fill_bytes(&mut msg);
+
+    let prefix = TurboShake128Prefix::new(&prefix);
+    let mut hasher = TurboShake128::from(prefix);
+
+    bencher.counter(ItemsCount::new(1usize)).bench_local(|| {
+        prefix.reset_to_prefix(&mut hasher);
+
+        let _ = hasher.absorb(divan::black_box(&msg));
+        let _ = hasher.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>();
+        let _ = hasher.squeeze(divan::black_box(&mut md));
+    });
+}
@@ -0,0 +1,151 @@
+//! Bit-interleaved implementation of Keccak-p\[1600, 12\] permutation, meant for 32 -bit targets, where each 64 -bit lane
+//! rotation of `keccak::permute` costs several 32 -bit instructions. Each 64 -bit lane is kept as two 32 -bit words, one holding
+//! even-indexed bits and another holding odd-indexed bits, s.t. each 64 -bit rotation becomes two 32 -bit rotations.
+//!
+//! See section 2.1 of Keccak implementation overview https://keccak.team/files/Keccak-implementation-3.2.pdf.
+
+use crate::keccak;
+
+/// Compile-time computed bit-interleaved round constants of Keccak-f\[1600\], as (even, odd) 32 -bit word pairs.
+const RC: [(u32, u32); keccak::MAX_ROUNDS] = compute_round_constants_table();
+
+/// Compile-time evaluable computation of bit-interleaved round constants, by interleaving Keccak-f\[1600\] round constants.
+const fn compute_round_constants_table() -> [(u32, u32); keccak::MAX_ROUNDS] {
+    let mut table = [(0u32, 0u32); keccak::MAX_ROUNDS];
+
+    let mut r_idx = 0;
+    while r_idx < keccak::MAX_ROUNDS {
+        let word = interleave(keccak::RC[r_idx]);
+        table[r_idx] = (word as u32, (word >> 32) as u32);
+
+        r_idx += 1;
+    }
+
+    table
+}
+
+/// Given a 64 -bit lane, moves its even-indexed bits to lower 32 -bits and odd-indexed bits to upper 32 -bits,
+/// keeping relative order of bits within each half, following section 7.2 of Hacker's Delight.
+#[inline(always)]
+pub const fn interleave(word: u64) -> u64 {
+    let mut x = word;
+    let mut t;
+
+    t = (x ^ (x >> 1)) & 0x2222222222222222;
+    x ^= t ^ (t << 1);
+    t = (x ^ (x >> 2)) & 0x0c0c0c0c0c0c0c0c;
+    x ^= t ^ (t << 2);
+    t = (x ^ (x >> 4)) & 0x00f000f000f000f0;
+    x ^= t ^ (t << 4);
+    t = (x ^ (x >> 8)) & 0x0000ff000000ff00;
+    x ^= t ^ (t << 8);
+    t = (x ^ (x >> 16)) & 0x00000000ffff0000;
+    x ^= t ^ (t << 16);
+
+    x
+}
+
+/// Inverse of `interleave`, given a bit-interleaved 64 -bit lane, it puts bits back in their original positions.
+#[inline(always)]
+pub const fn deinterleave(word: u64) -> u64 {
+    let mut x = word;
+    let mut t;
+
+    t = (x ^ (x >> 16)) & 0x00000000ffff0000;
+    x ^= t ^ (t << 16);
+    t = (x ^ (x >> 8)) & 0x0000ff000000ff00;
+    x ^= t ^ (t << 8);
+    t = (x ^ (x >> 4)) & 0x00f000f000f000f0;
+    x ^= t ^ (t << 4);
+    t = (x ^ (x >> 2)) & 0x0c0c0c0c0c0c0c0c;
+    x ^= t ^ (t << 2);
+    t = (x ^ (x >> 1)) & 0x2222222222222222;
+    x ^= t ^ (t << 1);
+
+    x
+}
+
+/// Rotates a bit-interleaved 64 -bit lane, given as (even, odd) 32 -bit word pair, leftwards by `r` bits. When r = 2k, both words
+/// are rotated by k bits. When r = 2k + 1, even bits become odd bits after rotating by k bits, while odd bits become even bits
+/// after rotating by k + 1 bits.
+#[inline(always)]
+const fn rotate_left(even: u32, odd: u32, r: usize) -> (u32, u32) {
+    let k = (r / 2) as u32;
+
+    if r % 2 == 0 {
+        (even.rotate_left(k), odd.rotate_left(k))
+    } else {
+        (odd.rotate_left(k + 1), even.rotate_left(k))
+    }
+}
+
+/// Keccak-p\[1600, 12\] round function, over bit-interleaved lanes, which applies all five step mapping functions in order,
+/// for round index `ridx` ( as per Keccak-f\[1600\] ), following section 3.3 of https://dx.doi.org/10.6028/NIST.FIPS.202.
+#[inline(always)]
+fn round(even: &mut [u32; keccak::LANE_CNT], odd: &mut [u32; keccak::LANE_CNT], ridx: usize) {
+    // θ
+    let mut c_even = [0u32; 5];
+    let mut c_odd = [0u32; 5];
+
+    for i in 0..keccak::LANE_CNT {
+        c_even[i % 5] ^= even[i];
+        c_odd[i % 5] ^= odd[i];
+    }
+
+    let mut d_even = [0u32; 5];
+    let mut d_odd = [0u32; 5];
+
+    for x in 0..5 {
+        let (rot_even, rot_odd) = rotate_left(c_even[(x + 1) % 5], c_odd[(x + 1) % 5], 1);
+
+        d_even[x] = c_even[(x + 4) % 5] ^ rot_even;
+        d_odd[x] = c_odd[(x + 4) % 5] ^ rot_odd;
+    }
+
+    // ρ and π, where π moves lane (x, y) to (y, 2x + 3y)
+    let mut b_even = [0u32; keccak::LANE_CNT];
+    let mut b_odd = [0u32; keccak::LANE_CNT];
+
+    for i in 0..keccak::LANE_CNT {
+        let x = i % 5;
+        let y = i / 5;
+        let dst = 5 * ((2 * x + 3 * y) % 5) + y;
+
+        (b_even[dst], b_odd[dst]) = rotate_left(even[i] ^ d_even[x], odd[i] ^ d_odd[x], keccak::ROT[i]);
+    }
+
+    // χ
+    for i in 0..keccak::LANE_CNT {
+        let x = i % 5;
+        let y = i - x;
+
+        even[i] = b_even[i] ^ (!b_even[y + (x + 1) % 5] & b_even[y + (x + 2) % 5]);
+        odd[i] = b_odd[i] ^ (!b_odd[y + (x + 1) % 5] & b_odd[y + (x + 2) % 5]);
+    }
+
+    // ι
+    even[0] ^= RC[ridx].0;
+    odd[0] ^= RC[ridx].1;
+}
+
+/// Keccak-p\[1600, 12\] permutation, applied on state s.t. each lane is bit-interleaved, i.e. lower 32 -bits of each word
+/// hold even-indexed bits and upper 32 -bits hold odd-indexed bits of the lane. Lanes need to be converted using `interleave`
+/// before and `deinterleave` after, which is why sponge keeps its state bit-interleaved and converts only while absorbing
+/// and squeezing, when this backend is selected.
+pub fn permute(state: &mut [u64; keccak::LANE_CNT]) {
+    let mut even = [0u32; keccak::LANE_CNT];
+    let mut odd = [0u32; keccak::LANE_CNT];
+
+    for i in 0..keccak::LANE_CNT {
+        even[i] = state[i] as u32;
+        odd[i] = (state[i] >> 32) as u32;
+    }
+
+    for ridx in (keccak::MAX_ROUNDS - keccak::ROUNDS)..keccak::MAX_ROUNDS {
+        round(&mut even, &mut odd, ridx);
+    }
+
+    for i in 0..keccak::LANE_CNT {
+        state[i] = ((odd[i] as u64) << 32) | (even[i] as u64);
+    }
+}
@@ -0,0 +1,262 @@
+//! Cyclist mode https://keccak.team/xoodyak.html, as specified in section 2 of Xoodyak specification, instantiated with
+//! Keccak-p\[1600, 12\] permutation, in place of Xoodoo\[12\].
+//!
+//! Rates are derived from the 200 -bytes state, the same way Xoodyak derives them from its 48 -bytes state.
+//!
+//! - Hash rate is 168 -bytes, leaving 256 -bits capacity.
+//! - Keyed absorb rate is 196 -bytes, leaving 4 -bytes for padding and domain separation.
+//! - Keyed squeeze rate is 176 -bytes, leaving 192 -bits capacity.
+//! - Ratchet absorbs 16 -bytes squeezed output.
+
+use crate::keccak;
+
+/// Hash rate, in bytes.
+const R_HASH: usize = keccak::STATE_BYTE_LEN - 32;
+
+/// Keyed absorb rate, in bytes.
+const R_KIN: usize = keccak::STATE_BYTE_LEN - 4;
+
+/// Keyed squeeze rate, in bytes.
+const R_KOUT: usize = keccak::STATE_BYTE_LEN - 24;
+
+/// Byte length of squeezed output, absorbed by ratchet.
+const L_RATCHET: usize = 16;
+
+/// Color bytes, used for domain separation of absorb calls.
+const CD_ABSORB: u8 = 0x03;
+const CD_ABSORB_KEY: u8 = 0x02;
+
+/// Color bytes, used for domain separation of squeeze calls.
+const CU_CRYPT: u8 = 0x80;
+const CU_SQUEEZE: u8 = 0x40;
+const CU_SQUEEZE_KEY: u8 = 0x20;
+const CU_RATCHET: u8 = 0x10;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Phase {
+    Up,
+    Down,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Hash,
+    Keyed,
+}
+
+/// Cyclist object, which covers hashing in hash mode, and MAC, authenticated encryption and ratcheting in keyed mode,
+/// through a sequence of `absorb`, `encrypt`, `decrypt`, `squeeze`, `squeeze_key` and `ratchet` calls, where each output
+/// depends on all previous inputs.
+///
+/// ```
+/// use turboshake::Cyclist;
+///
+/// let mut digest = [0u8; 32];
+/// let mut hasher = Cyclist::hash();
+/// hasher.absorb(b"message");
+/// hasher.squeeze(&mut digest);
+///
+/// let mut sender = Cyclist::keyed(b"secret key", b"key id", b"");
+/// sender.absorb(b"nonce");
+/// sender.absorb(b"associated data");
+///
+/// let mut msg = *b"plaintext";
+/// sender.encrypt(&mut msg);
+///
+/// let mut tag = [0u8; 16];
+/// sender.squeeze(&mut tag);
+///
+/// let mut receiver = Cyclist::keyed(b"secret key", b"key id", b"");
+/// receiver.absorb(b"nonce");
+/// receiver.absorb(b"associated data");
+/// receiver.decrypt(&mut msg);
+///
+/// let mut expected_tag = [0u8; 16];
+/// receiver.squeeze(&mut expected_tag);
+///
+/// assert_eq!(&msg, b"plaintext");
+/// assert_eq!(tag, expected_tag);
+/// ```
+#[derive(Clone)]
+pub struct Cyclist {
+    state: [u8; keccak::STATE_BYTE_LEN],
+    phase: Phase,
+    mode: Mode,
+    r_absorb: usize,
+    r_squeeze: usize,
+}
+
+impl Cyclist {
+    /// Creates a Cyclist object in hash mode.
+    pub fn hash() -> Self {
+        Self {
+            state: [0u8; keccak::STATE_BYTE_LEN],
+            phase: Phase::Up,
+            mode: Mode::Hash,
+            r_absorb: R_HASH,
+            r_squeeze: R_HASH,
+        }
+    }
+
+    /// Creates a Cyclist object in keyed mode, absorbing secret `key`, along with its identifier `id`, followed by
+    /// absorbing `counter` a byte at a time, if it's non-empty.
+    ///
+    /// # Panics
+    ///
+    /// If key is empty or `key.len() + id.len()` > 195.
+    pub fn keyed(key: &[u8], id: &[u8], counter: &[u8]) -> Self {
+        assert!(!key.is_empty(), "key must be non-empty");
+        assert!(key.len() + id.len() < R_KIN, "key and its identifier must be at most 195 bytes together");
+
+        let mut cyclist = Self::hash();
+        cyclist.mode = Mode::Keyed;
+        cyclist.r_absorb = R_KIN;
+        cyclist.r_squeeze = R_KOUT;
+
+        let mut key_block = [0u8; R_KIN];
+        key_block[..key.len()].copy_from_slice(key);
+        key_block[key.len()..key.len() + id.len()].copy_from_slice(id);
+        key_block[key.len() + id.len()] = id.len() as u8;
+
+        cyclist.absorb_any(&key_block[..key.len() + id.len() + 1], R_KIN, CD_ABSORB_KEY);
+        if !counter.is_empty() {
+            cyclist.absorb_any(counter, 1, 0x00);
+        }
+
+        cyclist
+    }
+
+    /// Absorbs `msg` into the state.
+    pub fn absorb(&mut self, msg: &[u8]) {
+        self.absorb_any(msg, self.r_absorb, CD_ABSORB);
+    }
+
+    /// Encrypts plaintext `data` in place.
+    ///
+    /// # Panics
+    ///
+    /// If it's not in keyed mode.
+    pub fn encrypt(&mut self, data: &mut [u8]) {
+        assert!(self.mode == Mode::Keyed, "encryption requires keyed mode");
+        self.crypt(data, false);
+    }
+
+    /// Decrypts ciphertext `data` in place.
+    ///
+    /// # Panics
+    ///
+    /// If it's not in keyed mode.
+    pub fn decrypt(&mut self, data: &mut [u8]) {
+        assert!(self.mode == Mode::Keyed, "decryption requires keyed mode");
+        self.crypt(data, true);
+    }
+
+    /// Squeezes `out.len()` -bytes output, which is a digest in hash mode and a MAC in keyed mode.
+    pub fn squeeze(&mut self, out: &mut [u8]) {
+        self.squeeze_any(out, CU_SQUEEZE);
+    }
+
+    /// Squeezes `out.len()` -bytes output, which is meant to be used as a key, say for a new Cyclist object.
+    ///
+    /// # Panics
+    ///
+    /// If it's not in keyed mode.
+    pub fn squeeze_key(&mut self, out: &mut [u8]) {
+        assert!(self.mode == Mode::Keyed, "squeezing a key requires keyed mode");
+        self.squeeze_any(out, CU_SQUEEZE_KEY);
+    }
+
+    /// Transforms the state, s.t. it can't be rolled back, for forward secrecy.
+    ///
+    /// # Panics
+    ///
+    /// If it's not in keyed mode.
+    pub fn ratchet(&mut self) {
+        assert!(self.mode == Mode::Keyed, "ratcheting requires keyed mode");
+
+        let mut squeezed = [0u8; L_RATCHET];
+        self.squeeze_any(&mut squeezed, CU_RATCHET);
+        self.absorb_any(&squeezed, self.r_absorb, 0x00);
+    }
+
+    /// Absorbs `data` in blocks of `rate` -bytes, where only first block carries color byte `cd`. Empty `data` is
+    /// absorbed as a single empty block.
+    fn absorb_any(&mut self, data: &[u8], rate: usize, cd: u8) {
+        let mut cd = cd;
+        let mut blocks = data.chunks(rate);
+        let mut block = blocks.next().unwrap_or_default();
+
+        loop {
+            if self.phase != Phase::Up {
+                self.up(&mut [], 0x00);
+            }
+            self.down(block, cd);
+
+            cd = 0x00;
+            match blocks.next() {
+                Some(next_block) => block = next_block,
+                None => break,
+            }
+        }
+    }
+
+    /// Encrypts or decrypts `data` in place, in blocks of keyed squeeze rate.
+    fn crypt(&mut self, data: &mut [u8], decrypt: bool) {
+        let mut cu = CU_CRYPT;
+        let mut keystream = [0u8; R_KOUT];
+
+        let mut blocks = data.chunks_mut(R_KOUT).peekable();
+        if blocks.peek().is_none() {
+            self.up(&mut [], cu);
+            self.down(&[], 0x00);
+            return;
+        }
+
+        for block in blocks {
+            let keystream = &mut keystream[..block.len()];
+            self.up(keystream, cu);
+
+            if decrypt {
+                block.iter_mut().zip(keystream.iter()).for_each(|(byte, &k)| *byte ^= k);
+                self.down(block, 0x00);
+            } else {
+                self.down(block, 0x00);
+                block.iter_mut().zip(keystream.iter()).for_each(|(byte, &k)| *byte ^= k);
+            }
+
+            cu = 0x00;
+        }
+    }
+
+    /// Squeezes `out.len()` -bytes, in blocks of squeeze rate, where only first block carries color byte `cu`.
+    fn squeeze_any(&mut self, out: &mut [u8], cu: u8) {
+        let mut blocks = out.chunks_mut(self.r_squeeze);
+        self.up(blocks.next().unwrap_or_default(), cu);
+
+        for block in blocks {
+            self.down(&[], 0x00);
+            self.up(block, 0x00);
+        }
+    }
+
+    /// Adds `block`, followed by a padding byte, along with color byte `cd`, to the state.
+    fn down(&mut self, block: &[u8], cd: u8) {
+        self.phase = Phase::Down;
+
+        self.state.iter_mut().zip(block).for_each(|(s, &b)| *s ^= b);
+        self.state[block.len()] ^= 0x01;
+        self.state[keccak::STATE_BYTE_LEN - 1] ^= if self.mode == Mode::Hash { cd & 0x01 } else { cd };
+    }
+
+    /// Adds color byte `cu` to the state, in keyed mode, permutes it, and fills `block` with first bytes of the state.
+    fn up(&mut self, block: &mut [u8], cu: u8) {
+        self.phase = Phase::Up;
+
+        if self.mode != Mode::Hash {
+            self.state[keccak::STATE_BYTE_LEN - 1] ^= cu;
+        }
+        keccak::permute_nr_bytes::<{ keccak::ROUNDS }>(&mut self.state);
+
+        block.copy_from_slice(&self.state[..block.len()]);
+    }
+}
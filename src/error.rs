@@ -1,10 +1,19 @@
-/// Errors encountered during absorbing, finalizing or squeezing from TurboShake instances.
+/// Errors encountered during absorbing, finalizing or squeezing from TurboShake instances, expanding messages using them, or authenticating messages.
+///
+/// It's marked non-exhaustive, as new variants get added along with new constructions, so match on it with a wildcard arm.
 #[derive(PartialEq)]
+#[non_exhaustive]
 pub enum TurboShakeError {
     /// Xof instance is still in the data absorption phase; `finalize()` must be called to start squeezing output.
     StillInDataAbsorptionPhase,
     /// Attempted to absorb more data or finalize after the data absorption phase was already finalized.
     DataAbsorptionPhaseAlreadyFinalized,
+    /// Requested more than 65535 -bytes output from `expand_message_xof` of RFC 9380.
+    ExpandedMessageTooLong,
+    /// Domain separation tag given to `expand_message_xof` or `hash_to_field` of RFC 9380 is empty.
+    EmptyDomainSeparationTag,
+    /// Received MAC or authentication tag doesn't match the expected one.
+    AuthenticationFailed,
 }
 
 impl std::fmt::Display for TurboShakeError {
@@ -12,6 +21,9 @@ impl std::fmt::Display for TurboShakeError {
         match self {
             TurboShakeError::StillInDataAbsorptionPhase => write!(f, "Must call `finalize` to start squeezing output"),
             TurboShakeError::DataAbsorptionPhaseAlreadyFinalized => write!(f, "Already finalized, only squeezing is possible now"),
+            TurboShakeError::ExpandedMessageTooLong => write!(f, "Expanded message can't be longer than 65535 bytes"),
+            TurboShakeError::EmptyDomainSeparationTag => write!(f, "Domain separation tag must not be empty"),
+            TurboShakeError::AuthenticationFailed => write!(f, "Authentication failed"),
         }
     }
 }
@@ -0,0 +1,219 @@
+//! `expand_message_xof` and `hash_to_field` of RFC 9380 https://datatracker.ietf.org/doc/html/rfc9380, generic over the
+//! extendable output function, s.t. hashing to elliptic curves can be instantiated with TurboSHAKE{128, 256}, in place of
+//! SHAKE{128, 256}.
+//!
+//! ```
+//! use turboshake::{TurboShake128, hash2field};
+//!
+//! // Prime modulus of P-256 base field, as big-endian bytes.
+//! let p = const_hex::decode("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff").unwrap();
+//!
+//! // Two field elements, each as 32 -bytes big-endian integer, as needed by hash_to_curve.
+//! let mut u = [0u8; 64];
+//! hash2field::hash_to_field::<TurboShake128>(b"message", b"MY-APP-V01-CS01", &p, &mut u).expect("must hash to field");
+//! ```
+
+use crate::{TurboShake128, TurboShake128Reader, TurboShake256, TurboShake256Reader, TurboShakeError, sampling::XofReader};
+
+/// Prefix of the message, hashed for reducing an oversized domain separation tag, as per section 5.3.3 of RFC 9380.
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// Maximum byte length of a domain separation tag, which can be used as is.
+const MAX_DST_BYTE_LEN: usize = 255;
+
+/// Maximum byte length of output of `expand_message_xof`.
+const MAX_EXPANDED_MESSAGE_BYTE_LEN: usize = u16::MAX as usize;
+
+/// Extendable output function, which can instantiate `expand_message_xof`, at its target security level.
+pub trait Xof: Default {
+    /// Target security level k, in bits.
+    const SECURITY_BITS: usize;
+
+    /// Reader of output, obtained after finalization.
+    type Reader: XofReader;
+
+    /// Absorbs `msg` into the sponge state.
+    fn absorb(&mut self, msg: &[u8]);
+
+    /// Finalizes the sponge state, returning a reader of output.
+    fn finalize(self) -> Self::Reader;
+}
+
+impl Xof for TurboShake128 {
+    const SECURITY_BITS: usize = 128;
+    type Reader = TurboShake128Reader;
+
+    fn absorb(&mut self, msg: &[u8]) {
+        TurboShake128::absorb(self, msg).expect("unfinalized TurboSHAKE128 instance must absorb");
+    }
+
+    fn finalize(self) -> Self::Reader {
+        self.fork_finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>()
+            .expect("unfinalized TurboSHAKE128 instance must be forkable")
+    }
+}
+
+impl Xof for TurboShake256 {
+    const SECURITY_BITS: usize = 256;
+    type Reader = TurboShake256Reader;
+
+    fn absorb(&mut self, msg: &[u8]) {
+        TurboShake256::absorb(self, msg).expect("unfinalized TurboSHAKE256 instance must absorb");
+    }
+
+    fn finalize(self) -> Self::Reader {
+        self.fork_finalize::<{ TurboShake256::DEFAULT_DOMAIN_SEPARATOR }>()
+            .expect("unfinalized TurboSHAKE256 instance must be forkable")
+    }
+}
+
+/// Expands `msg` into `out.len()` -bytes uniformly random output, following section 5.3.2 of RFC 9380. A domain separation
+/// tag longer than 255 -bytes is reduced to ⌈2k / 8⌉ -bytes, following section 5.3.3 of RFC 9380.
+///
+/// # Inputs
+///
+/// * `msg`: An arbitrary length (including empty) slice of bytes, to be expanded.
+/// * `dst`: Domain separation tag, which must be non-empty, as per section 3.1 of RFC 9380.
+/// * `out`: Output slice, to be filled with uniformly random bytes.
+///
+/// # Returns
+///
+/// * Returns `Ok(())` if output slice is filled with expanded message.
+///   Returns Err(TurboShakeError::ExpandedMessageTooLong) if output slice is longer than 65535 -bytes.
+///   Returns Err(TurboShakeError::EmptyDomainSeparationTag) if `dst` is empty.
+pub fn expand_message_xof<X: Xof>(msg: &[u8], dst: &[u8], out: &mut [u8]) -> Result<(), TurboShakeError> {
+    expand_message_xof_reader::<X>(msg, dst, out.len())?.squeeze(out);
+    Ok(())
+}
+
+/// Hashes `msg` to `out.len() / modulus.len()` elements of prime field GF(p), following section 5.2 of RFC 9380. Each
+/// element is computed by reducing L = ⌈(⌈log2(p)⌉ + k) / 8⌉ -bytes of `expand_message_xof` output, interpreted as a
+/// big-endian integer, modulo p, and written to `out` as a big-endian integer of `modulus.len()` -bytes. For an extension
+/// field of degree m, request count · m elements, where each consecutive m elements are coordinates of one element.
+///
+/// # Inputs
+///
+/// * `msg`: An arbitrary length (including empty) slice of bytes, to be hashed.
+/// * `dst`: Domain separation tag, which must be non-empty, as per section 3.1 of RFC 9380.
+/// * `modulus`: Prime modulus p, as big-endian bytes.
+/// * `out`: Output slice, to be filled with field elements.
+///
+/// # Returns
+///
+/// * Returns `Ok(())` if output slice is filled with field elements.
+///   Returns Err(TurboShakeError::ExpandedMessageTooLong) if count · L > 65535.
+///   Returns Err(TurboShakeError::EmptyDomainSeparationTag) if `dst` is empty.
+///
+/// # Panics
+///
+/// If p < 2 or `out.len()` is not a multiple of `modulus.len()`.
+pub fn hash_to_field<X: Xof>(msg: &[u8], dst: &[u8], modulus: &[u8], out: &mut [u8]) -> Result<(), TurboShakeError> {
+    let p = Modulus::new(modulus);
+    assert!(out.len() % modulus.len() == 0, "output length must be a multiple of modulus length");
+
+    let elem_byte_len = (p.num_bits + X::SECURITY_BITS).div_ceil(u8::BITS as usize);
+    let num_elems = out.len() / modulus.len();
+
+    let mut reader = expand_message_xof_reader::<X>(msg, dst, num_elems * elem_byte_len)?;
+    let mut uniform_bytes = vec![0u8; elem_byte_len];
+
+    for elem in out.chunks_exact_mut(modulus.len()) {
+        reader.squeeze(&mut uniform_bytes);
+        p.reduce(&uniform_bytes, elem);
+    }
+
+    Ok(())
+}
+
+/// Computes msg || I2OSP(len_in_bytes, 2) || DST_prime, returning a reader of `expand_message_xof` output.
+fn expand_message_xof_reader<X: Xof>(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<X::Reader, TurboShakeError> {
+    if len_in_bytes > MAX_EXPANDED_MESSAGE_BYTE_LEN {
+        return Err(TurboShakeError::ExpandedMessageTooLong);
+    }
+    if dst.is_empty() {
+        return Err(TurboShakeError::EmptyDomainSeparationTag);
+    }
+
+    let mut reduced_dst = [0u8; 64];
+    let dst = if dst.len() > MAX_DST_BYTE_LEN {
+        let reduced_dst = &mut reduced_dst[..(2 * X::SECURITY_BITS).div_ceil(u8::BITS as usize)];
+
+        let mut hasher = X::default();
+        hasher.absorb(OVERSIZE_DST_PREFIX);
+        hasher.absorb(dst);
+        hasher.finalize().squeeze(reduced_dst);
+
+        &*reduced_dst
+    } else {
+        dst
+    };
+
+    let mut hasher = X::default();
+    hasher.absorb(msg);
+    hasher.absorb(&(len_in_bytes as u16).to_be_bytes());
+    hasher.absorb(dst);
+    hasher.absorb(&[dst.len() as u8]);
+
+    Ok(hasher.finalize())
+}
+
+/// Prime modulus, as little-endian 64 -bit limbs.
+struct Modulus {
+    limbs: Vec<u64>,
+    num_bits: usize,
+}
+
+impl Modulus {
+    /// Parses modulus from big-endian bytes.
+    fn new(modulus: &[u8]) -> Self {
+        let limbs: Vec<u64> = modulus
+            .rchunks(8)
+            .map(|chunk| chunk.iter().fold(0u64, |acc, &byte| (acc << 8) | byte as u64))
+            .collect();
+
+        let num_bits = limbs
+            .iter()
+            .rposition(|&limb| limb != 0)
+            .map_or(0, |idx| idx * u64::BITS as usize + (u64::BITS - limbs[idx].leading_zeros()) as usize);
+        assert!(num_bits > 1, "modulus must be at least 2");
+
+        Self { limbs, num_bits }
+    }
+
+    /// Reduces big-endian integer `bytes` modulo p, writing result to `out`, as big-endian integer. It processes one bit at a
+    /// time, doubling the remainder and adding the bit, followed by a conditional subtraction of p, selected without branching
+    /// on the bits, s.t. timing doesn't depend on the value being reduced.
+    fn reduce(&self, bytes: &[u8], out: &mut [u8]) {
+        let mut rem = vec![0u64; self.limbs.len()];
+        let mut diff = vec![0u64; self.limbs.len()];
+
+        for &byte in bytes {
+            for bit_idx in (0..u8::BITS).rev() {
+                let mut carry = ((byte >> bit_idx) & 1) as u64;
+                for limb in rem.iter_mut() {
+                    let next_carry = *limb >> (u64::BITS - 1);
+                    *limb = (*limb << 1) | carry;
+                    carry = next_carry;
+                }
+
+                let mut borrow = 0u64;
+                for ((d, &r), &p) in diff.iter_mut().zip(&rem).zip(&self.limbs) {
+                    let (t, b0) = r.overflowing_sub(p);
+                    let (t, b1) = t.overflowing_sub(borrow);
+
+                    *d = t;
+                    borrow = (b0 | b1) as u64;
+                }
+
+                let select_diff = (carry | (borrow ^ 1)).wrapping_neg();
+                for (r, &d) in rem.iter_mut().zip(&diff) {
+                    *r ^= (*r ^ d) & select_diff;
+                }
+            }
+        }
+
+        for (byte_idx, byte) in out.iter_mut().rev().enumerate() {
+            *byte = (rem[byte_idx / 8] >> (8 * (byte_idx % 8))) as u8;
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::TurboShake256;
+
+/// Labelled key derivation function, built on top of TurboSHAKE256, following extract-then-expand paradigm of HKDF
+/// https://datatracker.ietf.org/doc/html/rfc5869.
+///
+/// - Extract: PRK = TurboSHAKE256(enc(salt) || enc(ikm), D = 0x20, 64), where enc(x) = len(x) as 8 -bytes little-endian || x.
+/// - Expand: OKM = TurboSHAKE256(enc(PRK) || enc(label) || L as 8 -bytes little-endian, D = 0x21, L).
+///
+/// Length-prefixing makes the encoding of inputs injective, so that no two distinct (salt, ikm) or (label, L) pairs get
+/// absorbed as same bytes, while dedicated domain separators keep both steps apart from each other and from any other
+/// use of TurboSHAKE256. As requested length L is absorbed too, outputs of different lengths are unrelated, instead of
+/// one being prefix of the other.
+///
+/// # Security
+///
+/// TurboSHAKE256 offers at most 256 -bits of security, so does this KDF. A derived key of L -bytes offers min(8 · L, 256)
+/// -bits of security, provided input keying material has at least that much min-entropy. When it's not uniformly random,
+/// say a Diffie-Hellman shared secret, use a salt, which doesn't need to be secret, but it should be chosen independently of
+/// input keying material. Reusing the extracted state for deriving several subkeys, each with a distinct label, is safe.
+///
+/// # Example
+///
+/// ```
+/// use turboshake::TurboKdf;
+///
+/// let kdf = TurboKdf::extract(b"salt", b"input keying material");
+///
+/// let mut enc_key = [0u8; 32];
+/// let mut mac_key = [0u8; 32];
+///
+/// kdf.expand(b"encryption key", &mut enc_key);
+/// kdf.expand(b"mac key", &mut mac_key);
+/// ```
+#[derive(Clone)]
+pub struct TurboKdf {
+    prk_state: TurboShake256,
+}
+
+impl TurboKdf {
+    /// Domain separator used in extract step.
+    pub const EXTRACT_DOMAIN_SEPARATOR: u8 = 0x20;
+    /// Domain separator used in expand step.
+    pub const EXPAND_DOMAIN_SEPARATOR: u8 = 0x21;
+    /// Byte length of the pseudo-random key, computed in extract step.
+    pub const PRK_BYTE_LEN: usize = 64;
+
+    /// Extracts a pseudo-random key from input keying material and salt, keeping the sponge state, which has absorbed it,
+    /// so that it's not re-absorbed, while expanding it into subkeys.
+    ///
+    /// # Inputs
+    ///
+    /// * `salt`: An arbitrary length (including empty) slice of bytes, which doesn't need to be secret.
+    /// * `ikm`: An arbitrary length (including empty) slice of bytes, holding input keying material.
+    ///
+    /// # Returns
+    ///
+    /// A `TurboKdf` object, which can be used for expanding extracted pseudo-random key.
+    pub fn extract(salt: &[u8], ikm: &[u8]) -> Self {
+        let mut hasher = TurboShake256::default();
+        absorb_encoded(&mut hasher, salt);
+        absorb_encoded(&mut hasher, ikm);
+
+        let mut prk = [0u8; Self::PRK_BYTE_LEN];
+        let mut reader = hasher
+            .fork_finalize::<{ Self::EXTRACT_DOMAIN_SEPARATOR }>()
+            .expect("unfinalized TurboSHAKE256 instance must be forkable");
+        reader.squeeze(&mut prk);
+
+        let mut encoded_prk = [0u8; 8 + Self::PRK_BYTE_LEN];
+        encoded_prk[..8].copy_from_slice(&(Self::PRK_BYTE_LEN as u64).to_le_bytes());
+        encoded_prk[8..].copy_from_slice(&prk);
+
+        Self {
+            prk_state: TurboShake256::with_prefix(&encoded_prk),
+        }
+    }
+
+    /// Expands extracted pseudo-random key into `okm.len()` -bytes output keying material, bound to `label`. It can be
+    /// called many times, for deriving several named subkeys.
+    ///
+    /// # Inputs
+    ///
+    /// * `label`: An arbitrary length (including empty) slice of bytes, naming the subkey to be derived.
+    /// * `okm`: An arbitrary length (including empty) mutable slice of bytes, to be filled with output keying material.
+    ///
+    /// # Returns
+    ///
+    /// Nothing, output slice is filled with output keying material.
+    pub fn expand(&self, label: &[u8], okm: &mut [u8]) {
+        let mut hasher = self.prk_state.clone();
+        absorb_encoded(&mut hasher, label);
+        absorb(&mut hasher, &(okm.len() as u64).to_le_bytes());
+
+        let mut reader = hasher
+            .fork_finalize::<{ Self::EXPAND_DOMAIN_SEPARATOR }>()
+            .expect("unfinalized TurboSHAKE256 instance must be forkable");
+        reader.squeeze(okm);
+    }
+}
+
+/// Absorbs `msg` into an unfinalized TurboSHAKE256 instance.
+fn absorb(hasher: &mut TurboShake256, msg: &[u8]) {
+    hasher.absorb(msg).expect("unfinalized TurboSHAKE256 instance must absorb");
+}
+
+/// Absorbs length-prefixed encoding of `msg` i.e. its length as 8 -bytes little-endian, followed by itself.
+fn absorb_encoded(hasher: &mut TurboShake256, msg: &[u8]) {
+    absorb(hasher, &(msg.len() as u64).to_le_bytes());
+    absorb(hasher, msg);
+}
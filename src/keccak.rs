@@ -7,29 +7,33 @@ pub const W: usize = 1 << L;
 /// \# -of lanes in keccak permutation state s.t. each lane is of 64 -bit width.
 pub const LANE_CNT: usize = 25;
 
+/// \# -of bytes in keccak permutation state.
+pub(crate) const STATE_BYTE_LEN: usize = LANE_CNT * W / u8::BITS as usize;
+
 /// \# -of rounds of Keccak permutation is applied per iteration i.e. it's Keccak-p\[1600, 12\].
-const ROUNDS: usize = 12;
+pub(crate) const ROUNDS: usize = 12;
 
 /// Maximum number of rounds that can be supported by Keccak-f\[1600\] permutation.
-const MAX_ROUNDS: usize = 12 + 2 * L;
+pub(crate) const MAX_ROUNDS: usize = 12 + 2 * L;
 
 /// Compile-time computed lane rotation factor table used when applying ρ step mapping function.
-const ROT: [usize; LANE_CNT] = compute_rotation_factors_table();
+pub(crate) const ROT: [usize; LANE_CNT] = compute_rotation_factors_table(W);
 
-/// Compile-time computed round constants table used when applying ι step mapping function.
-const RC: [u64; ROUNDS] = compute_round_constants_table();
+/// Compile-time computed round constants table used when applying ι step mapping function, indexed by
+/// round index of Keccak-f\[1600\] s.t. Keccak-p\[1600, n\] uses last n entries of this table.
+pub(crate) const RC: [u64; MAX_ROUNDS] = compute_round_constants_table(L);
 
 /// Compile-time evaluable function for generating leftwards circular rotation offset
 /// for lanes of the keccak state array, computed following step 3(a), 3(b) of algorithm 2
-/// in section 3.2.2 of https://dx.doi.org/10.6028/NIST.FIPS.202.
-const fn compute_rotation_factors_table() -> [usize; LANE_CNT] {
+/// in section 3.2.2 of https://dx.doi.org/10.6028/NIST.FIPS.202, for lanes of bit width `w`.
+const fn compute_rotation_factors_table(w: usize) -> [usize; LANE_CNT] {
     let mut table = [0usize; LANE_CNT];
 
     let mut x = 1;
     let mut y = 0;
     let mut t = 0;
     while t <= 23 {
-        table[y * 5 + x] = ((t + 1) * (t + 2) / 2) % W;
+        table[y * 5 + x] = ((t + 1) * (t + 2) / 2) % w;
 
         let y_prime = (2 * x + 3 * y) % 5;
         x = y;
@@ -80,15 +84,15 @@ const fn rc(t: usize) -> bool {
     ((r >> 7) & 1) == 1
 }
 
-/// Compile-time evaluable computation of a 64 -bit round constant, which is XOR-ed into
-/// the very first lane ( = lane(0, 0) ) of Keccak-p\[1600, 12\] permutation state.
+/// Compile-time evaluable computation of a round constant, which is XOR-ed into the very first lane ( = lane(0, 0) )
+/// of Keccak-p\[b, nr\] permutation state, with lanes of bit width 2^l. Only lowest 2^l bits of returned word can be set.
 ///
 /// Taken from https://github.com/itzmeanjan/sha3/blob/faef1bd6f/include/keccak.hpp#L93C1-L109C2
-const fn compute_round_constant(r_idx: usize) -> u64 {
+const fn compute_round_constant(r_idx: usize, l: usize) -> u64 {
     let mut rc_word = 0;
 
     let mut j = 0;
-    while j < (L + 1) {
+    while j < (l + 1) {
         let boff = (1usize << j) - 1;
         rc_word |= (rc(j + 7 * r_idx) as u64) << boff;
 
@@ -98,13 +102,14 @@ const fn compute_round_constant(r_idx: usize) -> u64 {
     rc_word
 }
 
-/// Compile-time evaluable computation of all round constants of Keccak-p\[1600, 12\] permutation.
-const fn compute_round_constants_table() -> [u64; ROUNDS] {
-    let mut table = [0u64; ROUNDS];
+/// Compile-time evaluable computation of round constants for first 24 round indices, with lanes of bit width 2^l.
+/// Keccak-f\[25 · 2^l\] permutation uses first 12 + 2l of them.
+const fn compute_round_constants_table(l: usize) -> [u64; MAX_ROUNDS] {
+    let mut table = [0u64; MAX_ROUNDS];
 
-    let mut r_idx = MAX_ROUNDS - ROUNDS;
+    let mut r_idx = 0;
     while r_idx < MAX_ROUNDS {
-        table[r_idx - ROUNDS] = compute_round_constant(r_idx);
+        table[r_idx] = compute_round_constant(r_idx, l);
         r_idx += 1;
     }
 
@@ -114,20 +119,26 @@ const fn compute_round_constants_table() -> [u64; ROUNDS] {
 /// Keccak-p\[1600, 12\] round function, which applies all five step mapping functions in order, for four consecutive rounds
 /// starting from round index `ridx`, mutating state array, following section 3.3 of https://dx.doi.org/10.6028/NIST.FIPS.202.
 ///
+/// Last of the four rounds computes only first `NUM_OUT_ROWS` rows of the output state, each row being five consecutive lanes.
+/// Pass 5 for computing all lanes, anything less is meant for the very last round, when only a short output is to be squeezed.
+///
 /// Adapted from https://github.com/itzmeanjan/sha3/blob/b6ce9069/include/sha3/internals/keccak.hpp#L140-L583
+#[cfg(any(test, not(any(feature = "small", feature = "lane-complementing"))))]
 #[inline(always)]
-fn roundx4(state: &mut [u64; LANE_CNT], ridx: usize) {
+const fn roundx4<const NUM_OUT_ROWS: usize>(state: &mut [u64; LANE_CNT], ridx: usize) {
     let mut c = [0u64; 5];
     let mut d = [0u64; 5];
     let mut t;
 
     // Round ridx + 0
-    for i in (0..LANE_CNT).step_by(5) {
+    let mut i = 0;
+    while i < LANE_CNT {
         c[0] ^= state[i];
         c[1] ^= state[i + 1];
         c[2] ^= state[i + 2];
         c[3] ^= state[i + 3];
         c[4] ^= state[i + 4];
+        i += 5;
     }
 
     d[0] = c[4] ^ c[1].rotate_left(1);
@@ -221,14 +232,16 @@ fn roundx4(state: &mut [u64; LANE_CNT], ridx: usize) {
     state[14] = c[4] ^ (c[1] & !c[0]);
 
     // Round ridx + 1
-    c.fill(0);
+    c = [0u64; 5];
 
-    for i in (0..LANE_CNT).step_by(5) {
+    let mut i = 0;
+    while i < LANE_CNT {
         c[0] ^= state[i];
         c[1] ^= state[i + 1];
         c[2] ^= state[i + 2];
         c[3] ^= state[i + 3];
         c[4] ^= state[i + 4];
+        i += 5;
     }
 
     d[0] = c[4] ^ c[1].rotate_left(1);
@@ -322,14 +335,16 @@ fn roundx4(state: &mut [u64; LANE_CNT], ridx: usize) {
     state[19] = c[4] ^ (c[1] & !c[0]);
 
     // Round ridx + 2
-    c.fill(0);
+    c = [0u64; 5];
 
-    for i in (0..LANE_CNT).step_by(5) {
+    let mut i = 0;
+    while i < LANE_CNT {
         c[0] ^= state[i];
         c[1] ^= state[i + 1];
         c[2] ^= state[i + 2];
         c[3] ^= state[i + 3];
         c[4] ^= state[i + 4];
+        i += 5;
     }
 
     d[0] = c[4] ^ c[1].rotate_left(1);
@@ -423,14 +438,16 @@ fn roundx4(state: &mut [u64; LANE_CNT], ridx: usize) {
     state[4] = c[4] ^ (c[1] & !c[0]);
 
     // Round ridx + 3
-    c.fill(0);
+    c = [0u64; 5];
 
-    for i in (0..LANE_CNT).step_by(5) {
+    let mut i = 0;
+    while i < LANE_CNT {
         c[0] ^= state[i];
         c[1] ^= state[i + 1];
         c[2] ^= state[i + 2];
         c[3] ^= state[i + 3];
         c[4] ^= state[i + 4];
+        i += 5;
     }
 
     d[0] = c[4] ^ c[1].rotate_left(1);
@@ -455,73 +472,81 @@ fn roundx4(state: &mut [u64; LANE_CNT], ridx: usize) {
     state[3] = c[3] ^ (c[0] & !c[4]);
     state[4] = c[4] ^ (c[1] & !c[0]);
 
-    t = state[5] ^ d[0];
-    c[2] = t.rotate_left(ROT[10] as u32);
-    t = state[6] ^ d[1];
-    c[3] = t.rotate_left(ROT[16] as u32);
-    t = state[7] ^ d[2];
-    c[4] = t.rotate_left(ROT[22] as u32);
-    t = state[8] ^ d[3];
-    c[0] = t.rotate_left(ROT[3] as u32);
-    t = state[9] ^ d[4];
-    c[1] = t.rotate_left(ROT[9] as u32);
-
-    state[5] = c[0] ^ (c[2] & !c[1]);
-    state[6] = c[1] ^ (c[3] & !c[2]);
-    state[7] = c[2] ^ (c[4] & !c[3]);
-    state[8] = c[3] ^ (c[0] & !c[4]);
-    state[9] = c[4] ^ (c[1] & !c[0]);
-
-    t = state[10] ^ d[0];
-    c[4] = t.rotate_left(ROT[20] as u32);
-    t = state[11] ^ d[1];
-    c[0] = t.rotate_left(ROT[1] as u32);
-    t = state[12] ^ d[2];
-    c[1] = t.rotate_left(ROT[7] as u32);
-    t = state[13] ^ d[3];
-    c[2] = t.rotate_left(ROT[13] as u32);
-    t = state[14] ^ d[4];
-    c[3] = t.rotate_left(ROT[19] as u32);
-
-    state[10] = c[0] ^ (c[2] & !c[1]);
-    state[11] = c[1] ^ (c[3] & !c[2]);
-    state[12] = c[2] ^ (c[4] & !c[3]);
-    state[13] = c[3] ^ (c[0] & !c[4]);
-    state[14] = c[4] ^ (c[1] & !c[0]);
-
-    t = state[15] ^ d[0];
-    c[1] = t.rotate_left(ROT[5] as u32);
-    t = state[16] ^ d[1];
-    c[2] = t.rotate_left(ROT[11] as u32);
-    t = state[17] ^ d[2];
-    c[3] = t.rotate_left(ROT[17] as u32);
-    t = state[18] ^ d[3];
-    c[4] = t.rotate_left(ROT[23] as u32);
-    t = state[19] ^ d[4];
-    c[0] = t.rotate_left(ROT[4] as u32);
+    if NUM_OUT_ROWS > 1 {
+        t = state[5] ^ d[0];
+        c[2] = t.rotate_left(ROT[10] as u32);
+        t = state[6] ^ d[1];
+        c[3] = t.rotate_left(ROT[16] as u32);
+        t = state[7] ^ d[2];
+        c[4] = t.rotate_left(ROT[22] as u32);
+        t = state[8] ^ d[3];
+        c[0] = t.rotate_left(ROT[3] as u32);
+        t = state[9] ^ d[4];
+        c[1] = t.rotate_left(ROT[9] as u32);
+
+        state[5] = c[0] ^ (c[2] & !c[1]);
+        state[6] = c[1] ^ (c[3] & !c[2]);
+        state[7] = c[2] ^ (c[4] & !c[3]);
+        state[8] = c[3] ^ (c[0] & !c[4]);
+        state[9] = c[4] ^ (c[1] & !c[0]);
+    }
 
-    state[15] = c[0] ^ (c[2] & !c[1]);
-    state[16] = c[1] ^ (c[3] & !c[2]);
-    state[17] = c[2] ^ (c[4] & !c[3]);
-    state[18] = c[3] ^ (c[0] & !c[4]);
-    state[19] = c[4] ^ (c[1] & !c[0]);
+    if NUM_OUT_ROWS > 2 {
+        t = state[10] ^ d[0];
+        c[4] = t.rotate_left(ROT[20] as u32);
+        t = state[11] ^ d[1];
+        c[0] = t.rotate_left(ROT[1] as u32);
+        t = state[12] ^ d[2];
+        c[1] = t.rotate_left(ROT[7] as u32);
+        t = state[13] ^ d[3];
+        c[2] = t.rotate_left(ROT[13] as u32);
+        t = state[14] ^ d[4];
+        c[3] = t.rotate_left(ROT[19] as u32);
+
+        state[10] = c[0] ^ (c[2] & !c[1]);
+        state[11] = c[1] ^ (c[3] & !c[2]);
+        state[12] = c[2] ^ (c[4] & !c[3]);
+        state[13] = c[3] ^ (c[0] & !c[4]);
+        state[14] = c[4] ^ (c[1] & !c[0]);
+    }
 
-    t = state[20] ^ d[0];
-    c[3] = t.rotate_left(ROT[15] as u32);
-    t = state[21] ^ d[1];
-    c[4] = t.rotate_left(ROT[21] as u32);
-    t = state[22] ^ d[2];
-    c[0] = t.rotate_left(ROT[2] as u32);
-    t = state[23] ^ d[3];
-    c[1] = t.rotate_left(ROT[8] as u32);
-    t = state[24] ^ d[4];
-    c[2] = t.rotate_left(ROT[14] as u32);
+    if NUM_OUT_ROWS > 3 {
+        t = state[15] ^ d[0];
+        c[1] = t.rotate_left(ROT[5] as u32);
+        t = state[16] ^ d[1];
+        c[2] = t.rotate_left(ROT[11] as u32);
+        t = state[17] ^ d[2];
+        c[3] = t.rotate_left(ROT[17] as u32);
+        t = state[18] ^ d[3];
+        c[4] = t.rotate_left(ROT[23] as u32);
+        t = state[19] ^ d[4];
+        c[0] = t.rotate_left(ROT[4] as u32);
+
+        state[15] = c[0] ^ (c[2] & !c[1]);
+        state[16] = c[1] ^ (c[3] & !c[2]);
+        state[17] = c[2] ^ (c[4] & !c[3]);
+        state[18] = c[3] ^ (c[0] & !c[4]);
+        state[19] = c[4] ^ (c[1] & !c[0]);
+    }
 
-    state[20] = c[0] ^ (c[2] & !c[1]);
-    state[21] = c[1] ^ (c[3] & !c[2]);
-    state[22] = c[2] ^ (c[4] & !c[3]);
-    state[23] = c[3] ^ (c[0] & !c[4]);
-    state[24] = c[4] ^ (c[1] & !c[0]);
+    if NUM_OUT_ROWS > 4 {
+        t = state[20] ^ d[0];
+        c[3] = t.rotate_left(ROT[15] as u32);
+        t = state[21] ^ d[1];
+        c[4] = t.rotate_left(ROT[21] as u32);
+        t = state[22] ^ d[2];
+        c[0] = t.rotate_left(ROT[2] as u32);
+        t = state[23] ^ d[3];
+        c[1] = t.rotate_left(ROT[8] as u32);
+        t = state[24] ^ d[4];
+        c[2] = t.rotate_left(ROT[14] as u32);
+
+        state[20] = c[0] ^ (c[2] & !c[1]);
+        state[21] = c[1] ^ (c[3] & !c[2]);
+        state[22] = c[2] ^ (c[4] & !c[3]);
+        state[23] = c[3] ^ (c[0] & !c[4]);
+        state[24] = c[4] ^ (c[1] & !c[0]);
+    }
 }
 
 /// Keccak-p\[1600, 12\] permutation, applying 12 rounds of permutation
@@ -530,11 +555,837 @@ fn roundx4(state: &mut [u64; LANE_CNT], ridx: usize) {
 ///
 /// Adapted from https://github.com/itzmeanjan/sha3/blob/b5e897ed/include/keccak.hpp#L253-L493
 #[inline(always)]
-pub fn permute(state: &mut [u64; LANE_CNT]) {
+pub const fn permute(state: &mut [u64; LANE_CNT]) {
+    permute_nr::<ROUNDS>(state);
+}
+
+/// Keccak-p\[1600, NUM_ROUNDS\] permutation, applying last `NUM_ROUNDS` rounds of Keccak-f\[1600\] on the state,
+/// following algorithm 7 defined in section 3.3 of https://dx.doi.org/10.6028/NIST.FIPS.202.
+///
+/// `NUM_ROUNDS` must be non-zero, not exceeding 24. Keccak-p\[1600, 24\] is Keccak-f\[1600\].
+/// Being a `const fn`, it can also be evaluated at compile-time.
+///
+/// By default it uses fully unrolled `roundx4`, while enabling `small` feature switches to loop-based `round`,
+/// trading speed for much smaller code-size. Enabling `lane-complementing` feature switches to lane complemented rounds,
+/// unless `small` feature is also enabled.
+#[inline(always)]
+pub const fn permute_nr<const NUM_ROUNDS: usize>(state: &mut [u64; LANE_CNT]) {
+    const { assert!(NUM_ROUNDS > 0 && NUM_ROUNDS <= MAX_ROUNDS) }
+
+    #[cfg(not(any(feature = "small", feature = "lane-complementing")))]
+    permute_nr_unrolled::<NUM_ROUNDS>(state);
+
+    #[cfg(all(not(feature = "small"), feature = "lane-complementing"))]
+    permute_nr_lane_complemented::<NUM_ROUNDS>(state);
+
+    #[cfg(feature = "small")]
+    permute_nr_rolled::<NUM_ROUNDS>(state);
+}
+
+/// Keccak-p\[1600, NUM_ROUNDS\] permutation, on state given as bytes, where each 8 consecutive bytes are a little-endian lane,
+/// for modes which process the state a byte at a time.
+#[inline(always)]
+pub(crate) fn permute_nr_bytes<const NUM_ROUNDS: usize>(state: &mut [u8; STATE_BYTE_LEN]) {
+    let mut lanes = lanes_from_bytes(state);
+    permute_nr::<NUM_ROUNDS>(&mut lanes);
+    bytes_from_lanes(&lanes, state);
+}
+
+/// Twisted Keccak-p\[1600, NUM_ROUNDS\] permutation i.e. π ∘ Keccak-p\[1600, NUM_ROUNDS\] ∘ π⁻¹, as defined in section 2.1
+/// of Ketje v2 specification https://keccak.team/ketje.html, on state given as bytes, where each 8 consecutive bytes are a
+/// little-endian lane.
+#[inline(always)]
+pub(crate) fn permute_twisted_nr_bytes<const NUM_ROUNDS: usize>(state: &mut [u8; STATE_BYTE_LEN]) {
+    let mut lanes = lanes_from_bytes(state);
+    pi_inverse(&mut lanes);
+    permute_nr::<NUM_ROUNDS>(&mut lanes);
+    pi(&mut lanes);
+    bytes_from_lanes(&lanes, state);
+}
+
+/// Interprets each 8 consecutive bytes of the state as a little-endian lane.
+#[inline(always)]
+fn lanes_from_bytes(state: &[u8; STATE_BYTE_LEN]) -> [u64; LANE_CNT] {
+    core::array::from_fn(|i| u64::from_le_bytes(state[i * 8..(i + 1) * 8].try_into().unwrap()))
+}
+
+/// Writes each lane of the state as 8 little-endian bytes.
+#[inline(always)]
+fn bytes_from_lanes(lanes: &[u64; LANE_CNT], state: &mut [u8; STATE_BYTE_LEN]) {
+    state
+        .chunks_exact_mut(8)
+        .zip(lanes)
+        .for_each(|(chunk, lane)| chunk.copy_from_slice(&lane.to_le_bytes()));
+}
+
+/// Keccak-p\[1600, NUM_ROUNDS\] permutation, built on top of fully unrolled `roundx4`. When `NUM_ROUNDS` is not a multiple
+/// of 4, leading `NUM_ROUNDS % 4` rounds are applied using loop-based `round`.
+#[cfg(any(test, not(any(feature = "small", feature = "lane-complementing"))))]
+#[inline(always)]
+pub(crate) const fn permute_nr_unrolled<const NUM_ROUNDS: usize>(state: &mut [u64; LANE_CNT]) {
     const STEP_BY: usize = 4;
-    const { assert!(ROUNDS % STEP_BY == 0) }
 
-    roundx4(state, 0);
-    roundx4(state, 4);
-    roundx4(state, 8);
+    let mut ridx = MAX_ROUNDS - NUM_ROUNDS;
+    while ridx < MAX_ROUNDS - NUM_ROUNDS + NUM_ROUNDS % STEP_BY {
+        round(state, ridx);
+        ridx += 1;
+    }
+
+    while ridx < MAX_ROUNDS {
+        roundx4::<5>(state, ridx);
+        ridx += STEP_BY;
+    }
+}
+
+/// Keccak-p\[1600, 12\] permutation, computing only first `NUM_OUT_ROWS` rows of the output state, by truncating its last
+/// round. It's meant to be applied for the last time, when squeezing a short output, so that rest of the lanes, which don't
+/// hold meaningful values, are never read.
+#[cfg(any(test, not(any(feature = "small", feature = "lane-complementing", target_pointer_width = "32"))))]
+#[inline(always)]
+pub(crate) const fn permute_truncated<const NUM_OUT_ROWS: usize>(state: &mut [u64; LANE_CNT]) {
+    const STEP_BY: usize = 4;
+    const { assert!(NUM_OUT_ROWS > 0 && NUM_OUT_ROWS <= 5) }
+
+    let mut ridx = MAX_ROUNDS - ROUNDS;
+    while ridx < MAX_ROUNDS - STEP_BY {
+        roundx4::<5>(state, ridx);
+        ridx += STEP_BY;
+    }
+
+    roundx4::<NUM_OUT_ROWS>(state, ridx);
+}
+
+/// Keccak-p\[1600, 12\] round function, which applies all five step mapping functions in order, for a single round with
+/// round index `ridx`, using loops over lanes and compile-time computed `ROT` and `RC` tables. It's a fraction of the size
+/// of `roundx4`, and it's kept out-of-line, so that it's emitted only once, when targeting flash-constrained devices.
+#[cfg(any(test, feature = "small", not(feature = "lane-complementing")))]
+#[inline(never)]
+const fn round(state: &mut [u64; LANE_CNT], ridx: usize) {
+    // θ
+    let mut c = [0u64; 5];
+
+    let mut i = 0;
+    while i < LANE_CNT {
+        c[i % 5] ^= state[i];
+        i += 1;
+    }
+
+    let mut d = [0u64; 5];
+
+    let mut x = 0;
+    while x < 5 {
+        d[x] = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+        x += 1;
+    }
+
+    // ρ and π, where π moves lane (x, y) to (y, 2x + 3y)
+    let mut b = [0u64; LANE_CNT];
+
+    let mut i = 0;
+    while i < LANE_CNT {
+        let x = i % 5;
+        let y = i / 5;
+
+        b[5 * ((2 * x + 3 * y) % 5) + y] = (state[i] ^ d[x]).rotate_left(ROT[i] as u32);
+        i += 1;
+    }
+
+    // χ
+    let mut i = 0;
+    while i < LANE_CNT {
+        let x = i % 5;
+        let y = i - x;
+
+        state[i] = b[i] ^ (!b[y + (x + 1) % 5] & b[y + (x + 2) % 5]);
+        i += 1;
+    }
+
+    // ι
+    state[0] ^= RC[ridx];
+}
+
+/// Keccak-p\[1600, NUM_ROUNDS\] permutation, built on top of loop-based `round`.
+#[cfg(any(test, feature = "small"))]
+pub(crate) const fn permute_nr_rolled<const NUM_ROUNDS: usize>(state: &mut [u64; LANE_CNT]) {
+    let mut ridx = MAX_ROUNDS - NUM_ROUNDS;
+    while ridx < MAX_ROUNDS {
+        round(state, ridx);
+        ridx += 1;
+    }
+}
+
+/// Lanes which are kept complemented, between rounds, by lane complementing transform, i.e. lanes (1, 0), (2, 0), (3, 1),
+/// (2, 2), (2, 3) and (0, 4). It brings down \# -of NOT operations in χ, from 25 to 5 per round.
+///
+/// See section 2.2 of Keccak implementation overview https://keccak.team/files/Keccak-implementation-3.2.pdf.
+#[cfg(any(test, feature = "dev", feature = "lane-complementing"))]
+const LANE_COMPLEMENTING_MASK: [u64; LANE_CNT] = {
+    let mut mask = [0u64; LANE_CNT];
+
+    mask[1] = u64::MAX;
+    mask[2] = u64::MAX;
+    mask[8] = u64::MAX;
+    mask[12] = u64::MAX;
+    mask[17] = u64::MAX;
+    mask[20] = u64::MAX;
+
+    mask
+};
+
+/// Keccak-p\[1600, 12\] round function, applying all five step mapping functions for round index `ridx`, on state with lanes
+/// complemented as per `LANE_COMPLEMENTING_MASK`, producing state with same lanes complemented. χ step uses a mix of AND and OR
+/// operations, needing only one NOT per plane, instead of five.
+///
+/// Adapted from `thetaRhoPiChiIota` macro, with `UseBebigokimisa`, of
+/// https://github.com/XKCP/XKCP/blob/master/lib/low/KeccakP-1600/Optimized/KeccakP-1600-64.macros.
+#[cfg(any(test, feature = "dev", feature = "lane-complementing"))]
+#[inline(always)]
+const fn round_lane_complemented(state: &mut [u64; LANE_CNT], ridx: usize) {
+    let mut c = [0u64; 5];
+    let mut d = [0u64; 5];
+    let mut b = [0u64; LANE_CNT];
+
+    // θ
+    c[0] = state[0] ^ state[5] ^ state[10] ^ state[15] ^ state[20];
+    c[1] = state[1] ^ state[6] ^ state[11] ^ state[16] ^ state[21];
+    c[2] = state[2] ^ state[7] ^ state[12] ^ state[17] ^ state[22];
+    c[3] = state[3] ^ state[8] ^ state[13] ^ state[18] ^ state[23];
+    c[4] = state[4] ^ state[9] ^ state[14] ^ state[19] ^ state[24];
+
+    d[0] = c[4] ^ c[1].rotate_left(1);
+    d[1] = c[0] ^ c[2].rotate_left(1);
+    d[2] = c[1] ^ c[3].rotate_left(1);
+    d[3] = c[2] ^ c[4].rotate_left(1);
+    d[4] = c[3] ^ c[0].rotate_left(1);
+
+    // ρ and π
+    b[0] = (state[0] ^ d[0]).rotate_left(ROT[0] as u32);
+    b[1] = (state[6] ^ d[1]).rotate_left(ROT[6] as u32);
+    b[2] = (state[12] ^ d[2]).rotate_left(ROT[12] as u32);
+    b[3] = (state[18] ^ d[3]).rotate_left(ROT[18] as u32);
+    b[4] = (state[24] ^ d[4]).rotate_left(ROT[24] as u32);
+    b[5] = (state[3] ^ d[3]).rotate_left(ROT[3] as u32);
+    b[6] = (state[9] ^ d[4]).rotate_left(ROT[9] as u32);
+    b[7] = (state[10] ^ d[0]).rotate_left(ROT[10] as u32);
+    b[8] = (state[16] ^ d[1]).rotate_left(ROT[16] as u32);
+    b[9] = (state[22] ^ d[2]).rotate_left(ROT[22] as u32);
+    b[10] = (state[1] ^ d[1]).rotate_left(ROT[1] as u32);
+    b[11] = (state[7] ^ d[2]).rotate_left(ROT[7] as u32);
+    b[12] = (state[13] ^ d[3]).rotate_left(ROT[13] as u32);
+    b[13] = (state[19] ^ d[4]).rotate_left(ROT[19] as u32);
+    b[14] = (state[20] ^ d[0]).rotate_left(ROT[20] as u32);
+    b[15] = (state[4] ^ d[4]).rotate_left(ROT[4] as u32);
+    b[16] = (state[5] ^ d[0]).rotate_left(ROT[5] as u32);
+    b[17] = (state[11] ^ d[1]).rotate_left(ROT[11] as u32);
+    b[18] = (state[17] ^ d[2]).rotate_left(ROT[17] as u32);
+    b[19] = (state[23] ^ d[3]).rotate_left(ROT[23] as u32);
+    b[20] = (state[2] ^ d[2]).rotate_left(ROT[2] as u32);
+    b[21] = (state[8] ^ d[3]).rotate_left(ROT[8] as u32);
+    b[22] = (state[14] ^ d[4]).rotate_left(ROT[14] as u32);
+    b[23] = (state[15] ^ d[0]).rotate_left(ROT[15] as u32);
+    b[24] = (state[21] ^ d[1]).rotate_left(ROT[21] as u32);
+
+    // χ, with lanes complemented as per `LANE_COMPLEMENTING_MASK`
+    state[0] = b[0] ^ (b[1] | b[2]);
+    state[1] = b[1] ^ (!b[2] | b[3]);
+    state[2] = b[2] ^ (b[3] & b[4]);
+    state[3] = b[3] ^ (b[4] | b[0]);
+    state[4] = b[4] ^ (b[0] & b[1]);
+    state[5] = b[5] ^ (b[6] | b[7]);
+    state[6] = b[6] ^ (b[7] & b[8]);
+    state[7] = b[7] ^ (b[8] | !b[9]);
+    state[8] = b[8] ^ (b[9] | b[5]);
+    state[9] = b[9] ^ (b[5] & b[6]);
+    state[10] = b[10] ^ (b[11] | b[12]);
+    state[11] = b[11] ^ (b[12] & b[13]);
+    state[12] = b[12] ^ (!b[13] & b[14]);
+    state[13] = !b[13] ^ (b[14] | b[10]);
+    state[14] = b[14] ^ (b[10] & b[11]);
+    state[15] = b[15] ^ (b[16] & b[17]);
+    state[16] = b[16] ^ (b[17] | b[18]);
+    state[17] = b[17] ^ (!b[18] | b[19]);
+    state[18] = !b[18] ^ (b[19] & b[15]);
+    state[19] = b[19] ^ (b[15] | b[16]);
+    state[20] = b[20] ^ (!b[21] & b[22]);
+    state[21] = !b[21] ^ (b[22] | b[23]);
+    state[22] = b[22] ^ (b[23] & b[24]);
+    state[23] = b[23] ^ (b[24] | b[20]);
+    state[24] = b[24] ^ (b[20] & b[21]);
+
+    // ι
+    state[0] ^= RC[ridx];
+}
+
+/// Keccak-p\[1600, NUM_ROUNDS\] permutation, applying lane complementing transform on entry, running rounds on
+/// lane complemented state and undoing the transform on exit. When `lane-complementing` feature is enabled,
+/// `permute_nr` uses it, which helps on targets without an AND-NOT instruction.
+#[cfg(any(test, feature = "dev", feature = "lane-complementing"))]
+#[inline(always)]
+pub const fn permute_nr_lane_complemented<const NUM_ROUNDS: usize>(state: &mut [u64; LANE_CNT]) {
+    const { assert!(NUM_ROUNDS > 0 && NUM_ROUNDS <= MAX_ROUNDS) }
+
+    let mut i = 0;
+    while i < LANE_CNT {
+        state[i] ^= LANE_COMPLEMENTING_MASK[i];
+        i += 1;
+    }
+
+    let mut ridx = MAX_ROUNDS - NUM_ROUNDS;
+    while ridx < MAX_ROUNDS {
+        round_lane_complemented(state, ridx);
+        ridx += 1;
+    }
+
+    let mut i = 0;
+    while i < LANE_CNT {
+        state[i] ^= LANE_COMPLEMENTING_MASK[i];
+        i += 1;
+    }
+}
+
+/// Step mapping functions, constituting a round of Keccak-p\[1600, 12\] permutation, in the order they're applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepMapping {
+    Theta,
+    Rho,
+    Pi,
+    Chi,
+    Iota,
+}
+
+/// θ step mapping function, XOR-ing each bit of the state with parities of two neighbouring columns,
+/// following algorithm 1 in section 3.2.1 of https://dx.doi.org/10.6028/NIST.FIPS.202.
+pub const fn theta(state: &mut [u64; LANE_CNT]) {
+    let mut c = [0u64; 5];
+
+    let mut i = 0;
+    while i < LANE_CNT {
+        c[i % 5] ^= state[i];
+        i += 1;
+    }
+
+    let d = [
+        c[4] ^ c[1].rotate_left(1),
+        c[0] ^ c[2].rotate_left(1),
+        c[1] ^ c[3].rotate_left(1),
+        c[2] ^ c[4].rotate_left(1),
+        c[3] ^ c[0].rotate_left(1),
+    ];
+
+    let mut i = 0;
+    while i < LANE_CNT {
+        state[i] ^= d[i % 5];
+        i += 1;
+    }
+}
+
+/// ρ step mapping function, rotating each lane leftwards by its rotation factor,
+/// following algorithm 2 in section 3.2.2 of https://dx.doi.org/10.6028/NIST.FIPS.202.
+pub const fn rho(state: &mut [u64; LANE_CNT]) {
+    let mut i = 0;
+    while i < LANE_CNT {
+        state[i] = state[i].rotate_left(ROT[i] as u32);
+        i += 1;
+    }
+}
+
+/// π step mapping function, moving lane (x + 3y, x) to (x, y),
+/// following algorithm 3 in section 3.2.3 of https://dx.doi.org/10.6028/NIST.FIPS.202.
+pub const fn pi(state: &mut [u64; LANE_CNT]) {
+    let tmp = *state;
+
+    let mut y = 0;
+    while y < 5 {
+        let mut x = 0;
+        while x < 5 {
+            state[5 * y + x] = tmp[5 * x + (x + 3 * y) % 5];
+            x += 1;
+        }
+
+        y += 1;
+    }
+}
+
+/// χ step mapping function, the only non-linear step, XOR-ing each lane with a function of two other lanes in its row,
+/// following algorithm 4 in section 3.2.4 of https://dx.doi.org/10.6028/NIST.FIPS.202.
+pub const fn chi(state: &mut [u64; LANE_CNT]) {
+    let mut y = 0;
+    while y < LANE_CNT {
+        let row = [state[y], state[y + 1], state[y + 2], state[y + 3], state[y + 4]];
+
+        let mut x = 0;
+        while x < 5 {
+            state[y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            x += 1;
+        }
+
+        y += 5;
+    }
+}
+
+/// ι step mapping function, XOR-ing round constant into lane (0, 0), following algorithm 6 in section 3.2.5 of
+/// https://dx.doi.org/10.6028/NIST.FIPS.202. Round index `ridx` is as per Keccak-f\[1600\] i.e. ridx ∈ [0, 24), s.t.
+/// Keccak-p\[1600, 12\] uses round indices [12, 24).
+pub const fn iota(state: &mut [u64; LANE_CNT], ridx: usize) {
+    state[0] ^= RC[ridx];
+}
+
+/// Keccak-p\[1600, NUM_ROUNDS\] permutation, applying each step mapping function one at a time and invoking `trace` with
+/// round index ( as per Keccak-f\[1600\] ), the step mapping function just applied and the resulting state. Meant for
+/// debugging and education, it produces same intermediate values as listed in Keccak team's KeccakF-1600-IntermediateValues.txt,
+/// when run with 24 rounds. Output is same as `permute_nr::<NUM_ROUNDS>`, though it's much slower.
+pub fn permute_traced<const NUM_ROUNDS: usize, F>(state: &mut [u64; LANE_CNT], mut trace: F)
+where
+    F: FnMut(usize, StepMapping, &[u64; LANE_CNT]),
+{
+    const { assert!(NUM_ROUNDS > 0 && NUM_ROUNDS <= MAX_ROUNDS) }
+
+    for ridx in (MAX_ROUNDS - NUM_ROUNDS)..MAX_ROUNDS {
+        theta(state);
+        trace(ridx, StepMapping::Theta, state);
+
+        rho(state);
+        trace(ridx, StepMapping::Rho, state);
+
+        pi(state);
+        trace(ridx, StepMapping::Pi, state);
+
+        chi(state);
+        trace(ridx, StepMapping::Chi, state);
+
+        iota(state, ridx);
+        trace(ridx, StepMapping::Iota, state);
+    }
+}
+
+/// Column parity plane of the keccak state is represented as 5 lanes, where lane x holds parity bits of column (x, z), for z ∈ [0, 64).
+/// Such a plane can be seen as an element of ring F2\[x, z\] / (x^5 + 1, z^64 + 1), in which effect of θ on column parities is
+/// multiplication by 1 + x + x^4·z. This routine multiplies two such elements.
+const fn mul_column_parity_planes(a: &[u64; 5], b: &[u64; 5]) -> [u64; 5] {
+    let mut res = [0u64; 5];
+
+    let mut x1 = 0;
+    while x1 < 5 {
+        let mut z1 = 0;
+        while z1 < W {
+            if (a[x1] >> z1) & 1 == 1 {
+                let mut x2 = 0;
+                while x2 < 5 {
+                    res[(x1 + x2) % 5] ^= b[x2].rotate_left(z1 as u32);
+                    x2 += 1;
+                }
+            }
+
+            z1 += 1;
+        }
+
+        x1 += 1;
+    }
+
+    res
+}
+
+/// Compile-time evaluable computation of multiplicative inverse of u = 1 + x + x^4·z, in ring F2\[x, z\] / (x^5 + 1, z^64 + 1),
+/// which is used for inverting θ step mapping function.
+///
+/// As squaring is linear in characteristic 2, u^64 = 1 + x + x^4 doesn't depend on z anymore. So u^-1 = u^63 · (1 + x + x^4)^-1,
+/// where the later inverse is found by exhaustively searching through all 32 polynomials of F2\[x\] / (x^5 + 1).
+const fn compute_theta_inverse_table() -> [u64; 5] {
+    let u = [1u64, 1, 0, 0, 2];
+    let v = [1u64, 1, 0, 0, 1];
+    let one = [1u64, 0, 0, 0, 0];
+
+    let mut v_inv = [0u64; 5];
+    let mut found = false;
+
+    let mut cand = 0;
+    while cand < 32 {
+        let mut w = [0u64; 5];
+
+        let mut x = 0;
+        while x < 5 {
+            w[x] = (cand >> x) & 1;
+            x += 1;
+        }
+
+        let prod = mul_column_parity_planes(&v, &w);
+        if prod[0] == one[0] && prod[1] == one[1] && prod[2] == one[2] && prod[3] == one[3] && prod[4] == one[4] {
+            v_inv = w;
+            found = true;
+        }
+
+        cand += 1;
+    }
+    assert!(found);
+
+    let mut res = v_inv;
+
+    let mut i = 0;
+    while i < 63 {
+        res = mul_column_parity_planes(&res, &u);
+        i += 1;
+    }
+
+    res
+}
+
+/// Compile-time computed inverse of the effect of θ on column parity plane, used when applying inverse θ step mapping function.
+const THETA_INV: [u64; 5] = compute_theta_inverse_table();
+
+/// Inverse of θ step mapping function. Column parities of the original state are recovered by multiplying column parities of
+/// current state with `THETA_INV`, from which θ-effect is recomputed and XOR-ed back into the state.
+#[inline(always)]
+fn theta_inverse(state: &mut [u64; LANE_CNT]) {
+    let mut c = [0u64; 5];
+    for i in (0..LANE_CNT).step_by(5) {
+        c[0] ^= state[i];
+        c[1] ^= state[i + 1];
+        c[2] ^= state[i + 2];
+        c[3] ^= state[i + 3];
+        c[4] ^= state[i + 4];
+    }
+
+    let c = mul_column_parity_planes(&THETA_INV, &c);
+
+    let d = [
+        c[4] ^ c[1].rotate_left(1),
+        c[0] ^ c[2].rotate_left(1),
+        c[1] ^ c[3].rotate_left(1),
+        c[2] ^ c[4].rotate_left(1),
+        c[3] ^ c[0].rotate_left(1),
+    ];
+
+    for i in 0..LANE_CNT {
+        state[i] ^= d[i % 5];
+    }
+}
+
+/// Inverse of ρ step mapping function, rotating each lane rightwards by its rotation factor.
+#[inline(always)]
+fn rho_inverse(state: &mut [u64; LANE_CNT]) {
+    for i in 0..LANE_CNT {
+        state[i] = state[i].rotate_right(ROT[i] as u32);
+    }
+}
+
+/// Inverse of π step mapping function. π moves lane (x + 3y, x) to (x, y), so this moves it back.
+#[inline(always)]
+fn pi_inverse(state: &mut [u64; LANE_CNT]) {
+    let tmp = *state;
+
+    for y in 0..5 {
+        for x in 0..5 {
+            state[5 * x + (x + 3 * y) % 5] = tmp[5 * y + x];
+        }
+    }
+}
+
+/// Inverse of χ step mapping function, applied on each row. Given output row b, input row a satisfies
+/// a\[x\] = b\[x\] ^ (!b\[x + 1\] & a\[x + 2\]), which can be solved by walking along the row in steps of -2,
+/// following `inverseChi` of KeccakTools https://github.com/KeccakTeam/KeccakTools.
+#[inline(always)]
+fn chi_inverse(state: &mut [u64; LANE_CNT]) {
+    for y in (0..LANE_CNT).step_by(5) {
+        let b = [state[y], state[y + 1], state[y + 2], state[y + 3], state[y + 4]];
+
+        for i in 0..6 {
+            let x = (3 * i) % 5;
+            state[y + x] = b[x] ^ (!b[(x + 1) % 5] & state[y + (x + 2) % 5]);
+        }
+    }
+}
+
+/// Inverse of Keccak-p\[1600, 12\] permutation i.e. `permute_inverse(permute(s)) == s`, for any state s.
+pub fn permute_inverse(state: &mut [u64; LANE_CNT]) {
+    permute_inverse_nr::<ROUNDS>(state);
+}
+
+/// Inverse of Keccak-p\[1600, NUM_ROUNDS\] permutation, applying inverse of ι, χ, π, ρ and θ, in that order,
+/// for each of last `NUM_ROUNDS` rounds of Keccak-f\[1600\], in reverse round order.
+pub fn permute_inverse_nr<const NUM_ROUNDS: usize>(state: &mut [u64; LANE_CNT]) {
+    const { assert!(NUM_ROUNDS > 0 && NUM_ROUNDS <= MAX_ROUNDS) }
+
+    for ridx in ((MAX_ROUNDS - NUM_ROUNDS)..MAX_ROUNDS).rev() {
+        iota(state, ridx);
+        chi_inverse(state);
+        pi_inverse(state);
+        rho_inverse(state);
+        theta_inverse(state);
+    }
+}
+
+/// Minimum and maximum number of shares, a state can be split into, by Boolean masking, i.e. masking order d ∈ \[1, 7\].
+pub const MIN_SHARES: usize = 2;
+pub const MAX_SHARES: usize = 8;
+
+/// Keccak-p\[1600, 12\] permutation, on a Boolean masked state, split into `SHARES` = d + 1 shares, s.t. the state is XOR
+/// of all shares, for hardening it against side-channel attacks, such as power analysis, up to order d.
+///
+/// Linear step mapping functions θ, ρ and π are applied on each share, while ι is applied only on first share. χ computes
+/// x ⊕ z ⊕ (y ∧ z), where x, y and z are lanes of a row, s.t. the only non-linear operation, AND, is computed using the
+/// masked multiplication of Ishai, Sahai and Wagner https://doi.org/10.1007/978-3-540-45146-4_27, which consumes
+/// d · (d + 1) / 2 fresh random 64 -bit words, returned by `random_u64`, for each lane. So, `random_u64` must draw from a
+/// cryptographically secure random number generator, seeded with fresh randomness, say a TurboSHAKE128 reader, which has
+/// absorbed random bytes sampled from operating system's random number generator.
+///
+/// Note, it's a portable implementation, which doesn't control how the compiler allocates registers and orders instructions,
+/// so resistance against side-channel attacks must be evaluated on the target.
+pub fn permute_masked<const SHARES: usize, F: FnMut() -> u64>(state: &mut [[u64; LANE_CNT]; SHARES], mut random_u64: F) {
+    const { assert!(SHARES >= MIN_SHARES && SHARES <= MAX_SHARES) }
+
+    for ridx in (MAX_ROUNDS - ROUNDS)..MAX_ROUNDS {
+        for share in state.iter_mut() {
+            theta(share);
+            rho(share);
+            pi(share);
+        }
+
+        chi_masked(state, &mut random_u64);
+        iota(&mut state[0], ridx);
+    }
+}
+
+/// χ step mapping function, on a Boolean masked state, computing each lane of a row as x ⊕ z ⊕ (y ∧ z), which is same as
+/// x ⊕ (¬y ∧ z), using masked multiplication for y ∧ z.
+fn chi_masked<const SHARES: usize, F: FnMut() -> u64>(state: &mut [[u64; LANE_CNT]; SHARES], random_u64: &mut F) {
+    for y in (0..LANE_CNT).step_by(5) {
+        let row: [[u64; 5]; SHARES] = core::array::from_fn(|s| core::array::from_fn(|x| state[s][y + x]));
+
+        for x in 0..5 {
+            let lane_y: [u64; SHARES] = core::array::from_fn(|s| row[s][(x + 1) % 5]);
+            let lane_z: [u64; SHARES] = core::array::from_fn(|s| row[s][(x + 2) % 5]);
+            let product = and_masked(&lane_y, &lane_z, random_u64);
+
+            for s in 0..SHARES {
+                state[s][y + x] = row[s][x] ^ lane_z[s] ^ product[s];
+            }
+        }
+    }
+}
+
+/// Masked multiplication of Ishai, Sahai and Wagner, computing shares of a ∧ b, given shares of a and b, using a fresh random
+/// word for each pair of shares.
+fn and_masked<const SHARES: usize, F: FnMut() -> u64>(a: &[u64; SHARES], b: &[u64; SHARES], random_u64: &mut F) -> [u64; SHARES] {
+    let mut c: [u64; SHARES] = core::array::from_fn(|i| a[i] & b[i]);
+
+    for i in 0..SHARES {
+        for j in (i + 1)..SHARES {
+            let r = random_u64();
+
+            c[i] ^= r;
+            c[j] ^= (r ^ (a[i] & b[j])) ^ (a[j] & b[i]);
+        }
+    }
+
+    c
+}
+
+/// Lane of Keccak-p\[b, nr\] permutation state, where b = 25 · w and w ∈ {8, 16, 32, 64} is bit width of the lane,
+/// following section 3.1 of https://dx.doi.org/10.6028/NIST.FIPS.202.
+pub trait Lane: Copy {
+    /// Logarithm base 2 of bit width of the lane.
+    const L: usize;
+
+    /// \# -of rounds of Keccak-f\[b\] permutation i.e. maximum number of rounds that can be applied.
+    const MAX_ROUNDS: usize = 12 + 2 * Self::L;
+
+    /// Lane rotation factor table used when applying ρ step mapping function, reduced modulo bit width of the lane.
+    const ROT: [usize; LANE_CNT] = compute_rotation_factors_table(1 << Self::L);
+
+    /// Round constants table used when applying ι step mapping function, truncated to bit width of the lane.
+    const RC: [u64; MAX_ROUNDS] = compute_round_constants_table(Self::L);
+
+    /// Lane with all bits unset.
+    const ZERO: Self;
+
+    /// Bitwise XOR of two lanes.
+    fn xor(self, rhs: Self) -> Self;
+
+    /// Bitwise AND of complement of this lane with `rhs` i.e. `!self & rhs`.
+    fn andn(self, rhs: Self) -> Self;
+
+    /// Leftwards circular rotation of the lane by `n` bits, where n < bit width of the lane.
+    fn rotl(self, n: u32) -> Self;
+
+    /// Converts a round constant, computed for this lane width, into a lane.
+    fn from_rc(rc: u64) -> Self;
+}
+
+macro_rules! impl_lane {
+    ($t:ty, $l:expr) => {
+        impl Lane for $t {
+            const L: usize = $l;
+            const ZERO: Self = 0;
+
+            #[inline(always)]
+            fn xor(self, rhs: Self) -> Self {
+                self ^ rhs
+            }
+
+            #[inline(always)]
+            fn andn(self, rhs: Self) -> Self {
+                !self & rhs
+            }
+
+            #[inline(always)]
+            fn rotl(self, n: u32) -> Self {
+                self.rotate_left(n)
+            }
+
+            #[inline(always)]
+            fn from_rc(rc: u64) -> Self {
+                rc as $t
+            }
+        }
+    };
+}
+
+impl_lane!(u8, 3);
+impl_lane!(u16, 4);
+impl_lane!(u32, 5);
+impl_lane!(u64, 6);
+
+/// Same lane of N independent Keccak-p\[1600, nr\] permutation states, each operation being applied on all of them,
+/// element-wise. Keeping them adjacent lets compiler auto-vectorize those operations, on targets with SIMD instructions.
+/// It's kept private to the crate, s.t. many states can only be permuted using `permute_many`, for N ∈ {2, 4, 8}.
+#[derive(Clone, Copy)]
+struct Lanes<const N: usize>([u64; N]);
+
+impl<const N: usize> Lane for Lanes<N> {
+    const L: usize = 6;
+    const ZERO: Self = Lanes([0u64; N]);
+
+    #[inline(always)]
+    fn xor(mut self, rhs: Self) -> Self {
+        for j in 0..N {
+            self.0[j] ^= rhs.0[j];
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn andn(mut self, rhs: Self) -> Self {
+        for j in 0..N {
+            self.0[j] = !self.0[j] & rhs.0[j];
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn rotl(mut self, n: u32) -> Self {
+        self.0.iter_mut().for_each(|lane| *lane = lane.rotate_left(n));
+        self
+    }
+
+    #[inline(always)]
+    fn from_rc(rc: u64) -> Self {
+        Lanes([rc; N])
+    }
+}
+
+/// Keccak-p\[b, nr\] round function over lanes of any supported bit width, which applies all five step mapping functions
+/// in order, using loops over lanes, following section 3.3 of https://dx.doi.org/10.6028/NIST.FIPS.202. Round index `ridx`
+/// is as per Keccak-f\[b\] i.e. ridx ∈ [0, 12 + 2l).
+#[inline(always)]
+fn round_generic<T: Lane>(state: &mut [T; LANE_CNT], ridx: usize) {
+    // θ
+    macro_rules! column_parity {
+        ($x:literal) => {
+            state[$x].xor(state[$x + 5]).xor(state[$x + 10]).xor(state[$x + 15]).xor(state[$x + 20])
+        };
+    }
+
+    let c = [column_parity!(0), column_parity!(1), column_parity!(2), column_parity!(3), column_parity!(4)];
+    let d = [
+        c[4].xor(c[1].rotl(1)),
+        c[0].xor(c[2].rotl(1)),
+        c[1].xor(c[3].rotl(1)),
+        c[2].xor(c[4].rotl(1)),
+        c[3].xor(c[0].rotl(1)),
+    ];
+
+    // ρ and π, where lane (x, y) moves to (y, 2x + 3y), s.t. b[5y + x] is computed from state[5x + (x + 3y) % 5]
+    macro_rules! rho_pi {
+        ($($src:literal),+) => {
+            [$(state[$src].xor(d[$src % 5]).rotl(T::ROT[$src] as u32)),+]
+        };
+    }
+
+    let b = rho_pi!(0, 6, 12, 18, 24, 3, 9, 10, 16, 22, 1, 7, 13, 19, 20, 4, 5, 11, 17, 23, 2, 8, 14, 15, 21);
+
+    // χ
+    macro_rules! chi {
+        ($($y:literal),+) => {
+            $(
+                state[$y] = b[$y].xor(b[$y + 1].andn(b[$y + 2]));
+                state[$y + 1] = b[$y + 1].xor(b[$y + 2].andn(b[$y + 3]));
+                state[$y + 2] = b[$y + 2].xor(b[$y + 3].andn(b[$y + 4]));
+                state[$y + 3] = b[$y + 3].xor(b[$y + 4].andn(b[$y]));
+                state[$y + 4] = b[$y + 4].xor(b[$y].andn(b[$y + 1]));
+            )+
+        };
+    }
+
+    chi!(0, 5, 10, 15, 20);
+
+    // ι
+    state[0] = state[0].xor(T::from_rc(T::RC[ridx]));
+}
+
+/// Keccak-p\[b, NUM_ROUNDS\] permutation, applying last `NUM_ROUNDS` rounds of Keccak-f\[b\] on state of dimension
+/// 5 x 5 x w, where w is bit width of the lane type `T`. Use `u8`, `u16`, `u32` or `u64` lanes for getting Keccak-p\[200, nr\],
+/// Keccak-p\[400, nr\], Keccak-p\[800, nr\] or Keccak-p\[1600, nr\], respectively, where 0 < nr <= 12 + 2l.
+///
+/// ```
+/// use turboshake::keccak;
+///
+/// // Keccak-p[800, 22] i.e. Keccak-f[800]
+/// let mut state = [0u32; keccak::LANE_CNT];
+/// keccak::permute_p::<u32, 22>(&mut state);
+/// ```
+pub fn permute_p<T: Lane, const NUM_ROUNDS: usize>(state: &mut [T; LANE_CNT]) {
+    const { assert!(NUM_ROUNDS > 0 && NUM_ROUNDS <= T::MAX_ROUNDS) }
+
+    for ridx in (T::MAX_ROUNDS - NUM_ROUNDS)..T::MAX_ROUNDS {
+        round_generic(state, ridx);
+    }
+}
+
+/// Keccak-p\[1600, 12\] permutation, applied on N independent states at once, where `state[i][j]` is lane i of state j and
+/// N ∈ {2, 4, 8}. It's a portable implementation, running all states through same round function, s.t. same lane of all
+/// states are processed together, which lets compiler auto-vectorize it, on any target with SIMD instructions. Whether it beats
+/// N calls to `permute` depends on the target, for example on x86_64 with AVX-512, 8 states get permuted ~7x faster, while
+/// with narrower vectors, it can be slower than the unrolled scalar permutation.
+///
+/// ```
+/// use turboshake::keccak;
+///
+/// let mut state = [[0u64; 4]; keccak::LANE_CNT];
+/// keccak::permute_many::<4>(&mut state);
+/// ```
+pub fn permute_many<const N: usize>(state: &mut [[u64; N]; LANE_CNT]) {
+    const { assert!(N == 2 || N == 4 || N == 8) }
+
+    let mut lanes: [Lanes<N>; LANE_CNT] = core::array::from_fn(|i| Lanes(state[i]));
+    permute_p::<Lanes<N>, ROUNDS>(&mut lanes);
+
+    state.iter_mut().zip(lanes).for_each(|(dst, lane)| *dst = lane.0);
+}
+
+/// Keccak-p\[1600, 12\] permutation, applied on two states at once, where `state[i][j]` is lane i of state j. Same lane of
+/// both states being adjacent in memory lets the WebAssembly SIMD128 backend keep them in a single 128 -bit vector register,
+/// when compiling for `wasm32` target with `simd128` target feature enabled. Otherwise, it falls back to `permute_many`.
+///
+/// ```
+/// use turboshake::keccak;
+///
+/// let mut state = [[0u64; 2]; keccak::LANE_CNT];
+/// keccak::permute_x2(&mut state);
+/// ```
+pub fn permute_x2(state: &mut [[u64; 2]; LANE_CNT]) {
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    crate::simd128::permute_x2(state);
+
+    #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+    permute_many::<2>(state);
 }
@@ -0,0 +1,283 @@
+//! Lake Keyak authenticated encryption, following Keyak v2 specification https://keccak.team/keyak.html, as Motorist mode
+//! instantiated with Keccak-p\[1600, 12\] permutation, a single piston, 256 -bits capacity and 128 -bits tag.
+//!
+//! It's built as layers, as in the specification.
+//!
+//! - Piston: holds the state, encrypting/ decrypting up to 168 -bytes and injecting up to 192 -bytes per permutation call,
+//!   while recording offsets and end-of-message marker in the state bytes right after the injection rate.
+//! - Engine: drives the piston, interleaving encryption of the message with injection of associated data.
+//! - Motorist: starts the engine with secret and unique value, and derives a tag after each wrap.
+//!
+//! As the secret and unique value is key pack of the secret key, followed by the nonce, keys can be at most 38 -bytes, while
+//! nonces can be at most 150 -bytes.
+
+use crate::{TurboShakeError, keccak};
+
+/// Width of a lane, in bytes.
+const W: usize = 8;
+
+/// Capacity, in bytes.
+const CAPACITY: usize = 32;
+
+/// Squeeze rate i.e. maximum number of bytes encrypted/ decrypted per permutation call.
+const RS: usize = W * ((keccak::STATE_BYTE_LEN - CAPACITY) / W);
+
+/// Absorb rate i.e. maximum number of bytes injected per permutation call.
+const RA: usize = W * ((keccak::STATE_BYTE_LEN - 4) / W);
+
+/// Offsets of state bytes, recording end-of-message marker, end offset of encryption and start/ end offsets of injection.
+const OFFSET_EOM: usize = RA;
+const OFFSET_CRYPT_END: usize = RA + 1;
+const OFFSET_INJECT_START: usize = RA + 2;
+const OFFSET_INJECT_END: usize = RA + 3;
+
+/// Number of pistons.
+const NUM_PISTONS: u8 = 1;
+
+/// Byte length of the key pack.
+const KEY_PACK_BYTE_LEN: usize = 40;
+
+/// Holds Keccak-p\[1600, 12\] permutation state.
+#[derive(Clone)]
+struct Piston {
+    state: [u8; keccak::STATE_BYTE_LEN],
+}
+
+impl Piston {
+    /// Encrypts or decrypts prefix of `data` in place, starting at `offset` of the state, until squeeze rate, returning number
+    /// of bytes processed. Plaintext is added to the state, s.t. the state holds ciphertext in both cases.
+    fn crypt(&mut self, data: &mut [u8], offset: usize, unwrap: bool) -> usize {
+        let num_bytes = data.len().min(RS - offset);
+
+        self.state[offset..offset + num_bytes].iter_mut().zip(data.iter_mut()).for_each(|(s, byte)| {
+            if unwrap {
+                let ct = *byte;
+                *byte ^= *s;
+                *s = ct;
+            } else {
+                *s ^= *byte;
+                *byte = *s;
+            }
+        });
+
+        self.state[OFFSET_CRYPT_END] ^= (offset + num_bytes) as u8;
+        num_bytes
+    }
+
+    /// Injects prefix of `data` into the state, until absorb rate, starting right after squeeze rate, if the piston is also
+    /// crypting, returning number of bytes injected.
+    fn inject(&mut self, data: &[u8], crypting: bool) -> usize {
+        let offset = if crypting { RS } else { 0 };
+        let num_bytes = data.len().min(RA - offset);
+
+        self.state[OFFSET_INJECT_START] ^= offset as u8;
+        self.state[offset..offset + num_bytes].iter_mut().zip(data).for_each(|(s, &byte)| *s ^= byte);
+        self.state[OFFSET_INJECT_END] ^= (offset + num_bytes) as u8;
+
+        num_bytes
+    }
+
+    /// Adds end-of-message marker, encoding byte length of the tag to be extracted, if `eom` is set, and permutes the state.
+    fn spark(&mut self, eom: bool, tag_len: usize) {
+        if eom {
+            self.state[OFFSET_EOM] ^= if tag_len == 0 { 0xff } else { tag_len as u8 };
+        }
+        keccak::permute_nr_bytes::<{ keccak::ROUNDS }>(&mut self.state);
+    }
+
+    /// Fills `tag` with first bytes of the state.
+    fn get_tag(&self, tag: &mut [u8]) {
+        tag.copy_from_slice(&self.state[..tag.len()]);
+    }
+}
+
+/// Drives the piston, keeping offset from which next encryption starts, as first bytes of the state may have been used as tag.
+#[derive(Clone)]
+struct Engine {
+    piston: Piston,
+    crypt_offset: usize,
+}
+
+impl Engine {
+    /// Permutes the state, after adding end-of-message marker, if `eom` is set.
+    fn spark(&mut self, eom: bool, tag_len: usize) {
+        self.piston.spark(eom, tag_len);
+        self.crypt_offset = tag_len;
+    }
+
+    /// Encrypts or decrypts `data` in place, while injecting associated data `ad`, followed by injecting rest of `ad`.
+    fn wrap(&mut self, ad: &[u8], data: &mut [u8], unwrap: bool) {
+        let mut ad_offset = 0;
+        let mut data_offset = 0;
+
+        while data_offset < data.len() {
+            data_offset += self.piston.crypt(&mut data[data_offset..], self.crypt_offset, unwrap);
+            ad_offset += self.piston.inject(&ad[ad_offset..], true);
+
+            if data_offset < data.len() || ad_offset < ad.len() {
+                self.spark(false, 0);
+            }
+        }
+
+        while ad_offset < ad.len() {
+            ad_offset += self.piston.inject(&ad[ad_offset..], false);
+
+            if ad_offset < ad.len() {
+                self.spark(false, 0);
+            }
+        }
+    }
+
+    /// Fills `tag` with first bytes of the state, after permuting it, with end-of-message marker encoding tag length.
+    fn get_tags(&mut self, tag: &mut [u8]) {
+        self.spark(true, tag.len());
+        self.piston.get_tag(tag);
+    }
+
+    /// Injects `data`, diversified by number of pistons and index of the piston, if `diversify` is set, into all pistons.
+    fn inject_collective(&mut self, data: &[u8], diversify: bool) {
+        let diversifier = [NUM_PISTONS, 0];
+        let diversifier = if diversify { &diversifier[..] } else { &[] };
+
+        let mut offset = 0;
+        let total_len = data.len() + diversifier.len();
+
+        loop {
+            let mut block = [0u8; RA];
+            let num_bytes = (total_len - offset).min(RA);
+
+            block[..num_bytes].iter_mut().enumerate().for_each(|(i, byte)| {
+                let idx = offset + i;
+                *byte = if idx < data.len() { data[idx] } else { diversifier[idx - data.len()] };
+            });
+
+            offset += self.piston.inject(&block[..num_bytes], false);
+            if offset == total_len {
+                break;
+            }
+            self.spark(false, 0);
+        }
+    }
+}
+
+/// Starts the engine with a secret and unique value, and derives a tag after each wrap.
+#[derive(Clone)]
+struct Motorist {
+    engine: Engine,
+}
+
+impl Motorist {
+    /// Byte length of the tag.
+    const TAG_BYTE_LEN: usize = 16;
+
+    /// Starts the engine, by injecting secret and unique value `suv`, without extracting a tag.
+    fn start_engine(suv: &[u8]) -> Self {
+        let mut motorist = Self {
+            engine: Engine {
+                piston: Piston {
+                    state: [0u8; keccak::STATE_BYTE_LEN],
+                },
+                crypt_offset: 0,
+            },
+        };
+
+        motorist.engine.inject_collective(suv, true);
+        motorist.engine.get_tags(&mut []);
+        motorist
+    }
+
+    /// Encrypts or decrypts `data` in place, along with associated data `ad`, returning the tag.
+    fn wrap(&mut self, ad: &[u8], data: &mut [u8], unwrap: bool) -> [u8; Self::TAG_BYTE_LEN] {
+        self.engine.wrap(ad, data, unwrap);
+
+        let mut tag = [0u8; Self::TAG_BYTE_LEN];
+        self.engine.get_tags(&mut tag);
+        tag
+    }
+}
+
+/// Lake Keyak session, started with a secret key and a nonce, which can wrap/ unwrap many messages, where each tag
+/// authenticates all messages wrapped so far. Both ends of a session must wrap and unwrap same sequence of messages.
+///
+/// ```
+/// use turboshake::LakeKeyak;
+///
+/// let mut sender = LakeKeyak::new(b"16 -bytes key ..", b"unique nonce");
+/// let mut receiver = LakeKeyak::new(b"16 -bytes key ..", b"unique nonce");
+///
+/// let mut msg = *b"plaintext";
+/// let tag = sender.seal(b"associated data", &mut msg);
+///
+/// assert!(receiver.open(b"associated data", &mut msg, &tag).is_ok());
+/// assert_eq!(&msg, b"plaintext");
+/// ```
+#[derive(Clone)]
+pub struct LakeKeyak {
+    motorist: Motorist,
+    failed: bool,
+}
+
+impl LakeKeyak {
+    /// Byte length of the authentication tag.
+    pub const TAG_BYTE_LEN: usize = Motorist::TAG_BYTE_LEN;
+    /// Maximum byte length of the secret key.
+    pub const MAX_KEY_BYTE_LEN: usize = KEY_PACK_BYTE_LEN - 2;
+    /// Maximum byte length of the nonce.
+    pub const MAX_NONCE_BYTE_LEN: usize = RA - KEY_PACK_BYTE_LEN - 2;
+
+    /// Starts a session, with secret `key` and `nonce`. A nonce must never be reused with same key.
+    ///
+    /// # Panics
+    ///
+    /// If key is longer than 38 -bytes or nonce is longer than 150 -bytes.
+    pub fn new(key: &[u8], nonce: &[u8]) -> Self {
+        assert!(key.len() <= Self::MAX_KEY_BYTE_LEN, "key must be at most 38 bytes");
+        assert!(nonce.len() <= Self::MAX_NONCE_BYTE_LEN, "nonce must be at most 150 bytes");
+
+        let mut suv = [0u8; KEY_PACK_BYTE_LEN + Self::MAX_NONCE_BYTE_LEN];
+        suv[0] = KEY_PACK_BYTE_LEN as u8;
+        suv[1..1 + key.len()].copy_from_slice(key);
+        suv[1 + key.len()] = 0x01;
+        suv[KEY_PACK_BYTE_LEN..KEY_PACK_BYTE_LEN + nonce.len()].copy_from_slice(nonce);
+
+        Self {
+            motorist: Motorist::start_engine(&suv[..KEY_PACK_BYTE_LEN + nonce.len()]),
+            failed: false,
+        }
+    }
+
+    /// Encrypts `data` in place, authenticating it along with associated data `ad`, returning the tag.
+    ///
+    /// # Panics
+    ///
+    /// If a previous `open` call of the session has failed.
+    pub fn seal(&mut self, ad: &[u8], data: &mut [u8]) -> [u8; Self::TAG_BYTE_LEN] {
+        assert!(!self.failed, "session must not be used after a failed open");
+        self.motorist.wrap(ad, data, false)
+    }
+
+    /// Decrypts `data` in place, verifying the tag over it and associated data `ad`, in constant-time.
+    ///
+    /// # Returns
+    ///
+    /// * Returns `Ok(())` if the tag is valid, leaving plaintext in `data`.
+    ///   Returns Err(TurboShakeError::AuthenticationFailed) if the tag is invalid, zeroing `data`, so that unauthenticated
+    ///   plaintext is never released. The session can't be used anymore, after a failure.
+    ///
+    /// # Panics
+    ///
+    /// If a previous `open` call of the session has failed.
+    pub fn open(&mut self, ad: &[u8], data: &mut [u8], tag: &[u8; Self::TAG_BYTE_LEN]) -> Result<(), TurboShakeError> {
+        assert!(!self.failed, "session must not be used after a failed open");
+        let computed_tag = self.motorist.wrap(ad, data, true);
+
+        let diff = computed_tag.iter().zip(tag).fold(0u8, |acc, (&a, &b)| acc | (a ^ b));
+        if diff == 0 {
+            Ok(())
+        } else {
+            data.fill(0);
+            self.failed = true;
+            Err(TurboShakeError::AuthenticationFailed)
+        }
+    }
+}
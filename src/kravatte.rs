@@ -0,0 +1,374 @@
+//! Kravatte deck function, following Farfalle construction https://keccak.team/farfalle.html, as instantiated by Kravatte
+//! Achouffe, with Keccak-p\[1600, 6\] permutation, along with its session authenticated encryption modes Kravatte-SANE and
+//! Kravatte-SANSE.
+//!
+//! - Key derivation: k = p(K || 10*), where p is Keccak-p\[1600, 6\].
+//! - Compression: each input string is padded with 10*, split into 200 -bytes blocks M_i, and accumulated as
+//!   x = Σ p(M_i ⊕ k_i), where k_0 = k and k_{i + 1} = roll_c(k_i). Masks keep rolling across strings, s.t. a sequence of
+//!   strings can be compressed incrementally.
+//! - Expansion: y = p(x), output block j is p(roll_e^j(y)) ⊕ k', where k' is the mask following the last compressed block.
+//!
+//! roll_c updates lanes of the last plane, while roll_e updates lanes of the last two planes, following Kravatte Achouffe.
+
+use crate::{TurboShakeError, keccak};
+
+/// Number of rounds of each permutation call.
+const ROUNDS: usize = 6;
+
+/// Applies Keccak-p\[1600, 6\] permutation.
+fn permute(state: &mut [u64; keccak::LANE_CNT]) {
+    keccak::permute_nr::<ROUNDS>(state);
+}
+
+/// Rolling function of compression layer, updating lanes (0, 4), .., (4, 4) as a linear feedback shift register, where
+/// x_0 ← (x_0 ⋘ 7) ⊕ x_1 ⊕ (x_1 ≫ 3), followed by shifting lanes by one position.
+fn roll_c(state: &mut [u64; keccak::LANE_CNT]) {
+    let x0 = state[20].rotate_left(7) ^ state[21] ^ (state[21] >> 3);
+    state.copy_within(21..25, 20);
+    state[24] = x0;
+}
+
+/// Rolling function of expansion layer, updating lanes (0, 3), .., (4, 4) as a non-linear feedback shift register, where
+/// x_0 ← (x_0 ⋘ 7) ⊕ (x_1 ⋘ 18) ⊕ (x_2 ∧ (x_1 ≫ 1)), followed by shifting lanes by one position.
+fn roll_e(state: &mut [u64; keccak::LANE_CNT]) {
+    let x0 = state[15].rotate_left(7) ^ state[16].rotate_left(18) ^ (state[17] & (state[16] >> 1));
+    state.copy_within(16..25, 15);
+    state[24] = x0;
+}
+
+/// Adds `bytes` to the state, as little-endian lanes.
+fn add_bytes(state: &mut [u64; keccak::LANE_CNT], bytes: &[u8]) {
+    bytes.iter().enumerate().for_each(|(i, &byte)| state[i / 8] ^= (byte as u64) << (8 * (i % 8)));
+}
+
+/// Kravatte deck function, keyed with a secret key, which compresses a sequence of input strings and expands them into an
+/// arbitrary length output, from an arbitrary offset. As compression is incremental, output for a sequence of strings can be
+/// computed, after appending a string to a sequence, without compressing the sequence again.
+///
+/// ```
+/// use turboshake::Kravatte;
+///
+/// let mut kravatte = Kravatte::new(b"secret key");
+/// kravatte.compress(b"first string");
+///
+/// let mut mac = [0u8; 32];
+/// kravatte.expand(0, &mut mac);
+///
+/// kravatte.compress(b"second string");
+/// kravatte.expand(0, &mut mac);
+/// ```
+#[derive(Clone)]
+pub struct Kravatte {
+    mask: [u64; keccak::LANE_CNT],
+    accumulator: [u64; keccak::LANE_CNT],
+}
+
+impl Kravatte {
+    /// Derives key mask from secret `key`.
+    ///
+    /// # Panics
+    ///
+    /// If key is longer than 199 -bytes.
+    pub fn new(key: &[u8]) -> Self {
+        assert!(key.len() < keccak::STATE_BYTE_LEN, "key must be at most 199 bytes");
+
+        let mut mask = [0u64; keccak::LANE_CNT];
+        add_bytes(&mut mask, key);
+        mask[key.len() / 8] ^= 1 << (8 * (key.len() % 8));
+        permute(&mut mask);
+
+        Self {
+            mask,
+            accumulator: [0u64; keccak::LANE_CNT],
+        }
+    }
+
+    /// Appends `msg` to the sequence of compressed input strings.
+    pub fn compress(&mut self, msg: &[u8]) {
+        self.compress_with_suffix(msg, 0, 0);
+    }
+
+    /// Fills `out` with output of the deck function, for the sequence of strings compressed so far, skipping its first
+    /// `offset` -bytes.
+    pub fn expand(&self, offset: usize, out: &mut [u8]) {
+        let mut y = self.accumulator;
+        permute(&mut y);
+
+        for _ in 0..offset / keccak::STATE_BYTE_LEN {
+            roll_e(&mut y);
+        }
+
+        let mut skip = offset % keccak::STATE_BYTE_LEN;
+        let mut out_offset = 0;
+
+        while out_offset < out.len() {
+            let mut z = y;
+            permute(&mut z);
+            z.iter_mut().zip(&self.mask).for_each(|(lane, &k)| *lane ^= k);
+
+            let num_bytes = (keccak::STATE_BYTE_LEN - skip).min(out.len() - out_offset);
+            out[out_offset..out_offset + num_bytes]
+                .iter_mut()
+                .enumerate()
+                .for_each(|(i, byte)| *byte = (z[(skip + i) / 8] >> (8 * ((skip + i) % 8))) as u8);
+
+            out_offset += num_bytes;
+            skip = 0;
+            roll_e(&mut y);
+        }
+    }
+
+    /// Appends `msg`, followed by `suffix_bit_len` least significant bits of `suffix`, to the sequence of compressed input
+    /// strings, padding it with 10*.
+    fn compress_with_suffix(&mut self, msg: &[u8], suffix: u8, suffix_bit_len: usize) {
+        let mut blocks = msg.chunks_exact(keccak::STATE_BYTE_LEN);
+
+        for block in &mut blocks {
+            self.compress_block(block, None);
+        }
+
+        let last_byte = (suffix & ((1 << suffix_bit_len) - 1)) | (1 << suffix_bit_len);
+        self.compress_block(blocks.remainder(), Some(last_byte));
+    }
+
+    /// Accumulates permuted `block`, followed by `last_byte`, if any, masked with current mask, and rolls the mask.
+    fn compress_block(&mut self, block: &[u8], last_byte: Option<u8>) {
+        let mut state = self.mask;
+        add_bytes(&mut state, block);
+        if let Some(last_byte) = last_byte {
+            state[block.len() / 8] ^= (last_byte as u64) << (8 * (block.len() % 8));
+        }
+        permute(&mut state);
+
+        self.accumulator.iter_mut().zip(&state).for_each(|(x, &s)| *x ^= s);
+        roll_c(&mut self.mask);
+    }
+}
+
+/// Compares tags in constant-time, zeroing `data` on mismatch.
+fn verify_tag<const N: usize>(computed_tag: &[u8; N], tag: &[u8; N], data: &mut [u8]) -> Result<(), TurboShakeError> {
+    let diff = computed_tag.iter().zip(tag).fold(0u8, |acc, (&a, &b)| acc | (a ^ b));
+    if diff == 0 {
+        Ok(())
+    } else {
+        data.fill(0);
+        Err(TurboShakeError::AuthenticationFailed)
+    }
+}
+
+/// Kravatte-SANE session authenticated encryption, with a nonce, following Deck-SANE mode of Farfalle. Session history is
+/// the sequence of strings compressed by Kravatte, where each message is encrypted with output of the deck function over
+/// history, skipping its first 16 -bytes, which are the tag. Both ends of a session must wrap and unwrap same sequence of
+/// messages.
+///
+/// ```
+/// use turboshake::KravatteSane;
+///
+/// let (mut sender, sender_tag) = KravatteSane::new(b"secret key", b"unique nonce");
+/// let (mut receiver, receiver_tag) = KravatteSane::new(b"secret key", b"unique nonce");
+/// assert_eq!(sender_tag, receiver_tag);
+///
+/// let mut msg = *b"plaintext";
+/// let tag = sender.seal(b"associated data", &mut msg);
+///
+/// assert!(receiver.open(b"associated data", &mut msg, &tag).is_ok());
+/// assert_eq!(&msg, b"plaintext");
+/// ```
+#[derive(Clone)]
+pub struct KravatteSane {
+    kravatte: Kravatte,
+    e: u8,
+    failed: bool,
+}
+
+impl KravatteSane {
+    /// Byte length of the authentication tag, i.e. t = 128 -bits of Kravatte-SANE.
+    pub const TAG_BYTE_LEN: usize = 16;
+
+    /// Starts a session, with secret `key` and `nonce`, returning it along with the tag of the nonce. A nonce must never be
+    /// reused with same key.
+    ///
+    /// # Panics
+    ///
+    /// If key is longer than 199 -bytes.
+    pub fn new(key: &[u8], nonce: &[u8]) -> (Self, [u8; Self::TAG_BYTE_LEN]) {
+        let mut kravatte = Kravatte::new(key);
+        kravatte.compress(nonce);
+
+        let mut tag = [0u8; Self::TAG_BYTE_LEN];
+        kravatte.expand(0, &mut tag);
+
+        (Self { kravatte, e: 0, failed: false }, tag)
+    }
+
+    /// Encrypts `data` in place, authenticating it along with associated data `ad` and session history, returning the tag.
+    ///
+    /// # Panics
+    ///
+    /// If a previous `open` call of the session has failed.
+    pub fn seal(&mut self, ad: &[u8], data: &mut [u8]) -> [u8; Self::TAG_BYTE_LEN] {
+        assert!(!self.failed, "session must not be used after a failed open");
+        self.wrap(ad, data, false)
+    }
+
+    /// Decrypts `data` in place, verifying the tag over it, associated data `ad` and session history, in constant-time.
+    ///
+    /// # Returns
+    ///
+    /// * Returns `Ok(())` if the tag is valid, leaving plaintext in `data`.
+    ///   Returns Err(TurboShakeError::AuthenticationFailed) if the tag is invalid, zeroing `data`, so that unauthenticated
+    ///   plaintext is never released. The session can't be used anymore, after a failure.
+    ///
+    /// # Panics
+    ///
+    /// If a previous `open` call of the session has failed.
+    pub fn open(&mut self, ad: &[u8], data: &mut [u8], tag: &[u8; Self::TAG_BYTE_LEN]) -> Result<(), TurboShakeError> {
+        assert!(!self.failed, "session must not be used after a failed open");
+
+        let computed_tag = self.wrap(ad, data, true);
+        let result = verify_tag(&computed_tag, tag, data);
+
+        self.failed = result.is_err();
+        result
+    }
+
+    /// Encrypts or decrypts `data` in place, using output of the deck function over history, skipping the tag, followed by
+    /// appending `ad || 0 || e` and `ciphertext || 1 || e` to history, returning the tag over it.
+    fn wrap(&mut self, ad: &[u8], data: &mut [u8], decrypt: bool) -> [u8; Self::TAG_BYTE_LEN] {
+        let mut keystream = vec![0u8; data.len()];
+        self.kravatte.expand(Self::TAG_BYTE_LEN, &mut keystream);
+
+        if !ad.is_empty() || data.is_empty() {
+            self.kravatte.compress_with_suffix(ad, self.e << 1, 2);
+        }
+
+        if !data.is_empty() {
+            if decrypt {
+                self.kravatte.compress_with_suffix(data, 0b01 | (self.e << 1), 2);
+                data.iter_mut().zip(&keystream).for_each(|(byte, &k)| *byte ^= k);
+            } else {
+                data.iter_mut().zip(&keystream).for_each(|(byte, &k)| *byte ^= k);
+                self.kravatte.compress_with_suffix(data, 0b01 | (self.e << 1), 2);
+            }
+        }
+
+        let mut tag = [0u8; Self::TAG_BYTE_LEN];
+        self.kravatte.expand(0, &mut tag);
+        self.e ^= 1;
+
+        tag
+    }
+}
+
+/// Kravatte-SANSE session authenticated encryption, without a nonce, following Deck-SANSE mode of Farfalle, which is
+/// resistant to misuse, as it's a synthetic initialization vector scheme. The tag is computed over plaintext, appended to
+/// history, while the message is encrypted with output of the deck function over tag, appended to history. So, wrapping
+/// same message twice, in same position of two sessions, with same history, only reveals that they are equal. Both ends of
+/// a session must wrap and unwrap same sequence of messages.
+///
+/// ```
+/// use turboshake::KravatteSanse;
+///
+/// let mut sender = KravatteSanse::new(b"secret key");
+/// let mut receiver = KravatteSanse::new(b"secret key");
+///
+/// let mut msg = *b"plaintext";
+/// let tag = sender.seal(b"associated data", &mut msg);
+///
+/// assert!(receiver.open(b"associated data", &mut msg, &tag).is_ok());
+/// assert_eq!(&msg, b"plaintext");
+/// ```
+#[derive(Clone)]
+pub struct KravatteSanse {
+    kravatte: Kravatte,
+    e: u8,
+    failed: bool,
+}
+
+impl KravatteSanse {
+    /// Byte length of the authentication tag, i.e. t = 256 -bits of Kravatte-SANSE, which is twice as long as that of
+    /// Kravatte-SANE, as it also serves as synthetic initialization vector.
+    pub const TAG_BYTE_LEN: usize = 32;
+
+    /// Starts a session, with secret `key`.
+    ///
+    /// # Panics
+    ///
+    /// If key is longer than 199 -bytes.
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            kravatte: Kravatte::new(key),
+            e: 0,
+            failed: false,
+        }
+    }
+
+    /// Encrypts `data` in place, authenticating it along with associated data `ad` and session history, returning the tag.
+    ///
+    /// # Panics
+    ///
+    /// If a previous `open` call of the session has failed.
+    pub fn seal(&mut self, ad: &[u8], data: &mut [u8]) -> [u8; Self::TAG_BYTE_LEN] {
+        assert!(!self.failed, "session must not be used after a failed open");
+        self.absorb_ad(ad, data.is_empty());
+
+        let mut plaintext_history = self.kravatte.clone();
+        plaintext_history.compress_with_suffix(data, 0b010 | (self.e << 2), 3);
+
+        let mut tag = [0u8; Self::TAG_BYTE_LEN];
+        plaintext_history.expand(0, &mut tag);
+
+        self.crypt(&tag, data);
+        self.kravatte = plaintext_history;
+        self.e ^= 1;
+
+        tag
+    }
+
+    /// Decrypts `data` in place, verifying the tag over it, associated data `ad` and session history, in constant-time.
+    ///
+    /// # Returns
+    ///
+    /// * Returns `Ok(())` if the tag is valid, leaving plaintext in `data`.
+    ///   Returns Err(TurboShakeError::AuthenticationFailed) if the tag is invalid, zeroing `data`, so that unauthenticated
+    ///   plaintext is never released. The session can't be used anymore, after a failure.
+    ///
+    /// # Panics
+    ///
+    /// If a previous `open` call of the session has failed.
+    pub fn open(&mut self, ad: &[u8], data: &mut [u8], tag: &[u8; Self::TAG_BYTE_LEN]) -> Result<(), TurboShakeError> {
+        assert!(!self.failed, "session must not be used after a failed open");
+        self.absorb_ad(ad, data.is_empty());
+        self.crypt(tag, data);
+
+        let mut plaintext_history = self.kravatte.clone();
+        plaintext_history.compress_with_suffix(data, 0b010 | (self.e << 2), 3);
+
+        let mut computed_tag = [0u8; Self::TAG_BYTE_LEN];
+        plaintext_history.expand(0, &mut computed_tag);
+
+        self.kravatte = plaintext_history;
+        self.e ^= 1;
+
+        let result = verify_tag(&computed_tag, tag, data);
+        self.failed = result.is_err();
+        result
+    }
+
+    /// Appends `ad || 0 || e` to history, if it's non-empty or the message is empty.
+    fn absorb_ad(&mut self, ad: &[u8], empty_msg: bool) {
+        if !ad.is_empty() || empty_msg {
+            self.kravatte.compress_with_suffix(ad, self.e << 1, 2);
+        }
+    }
+
+    /// Encrypts or decrypts `data` in place, using output of the deck function over `tag || 11 || e`, appended to history.
+    fn crypt(&self, tag: &[u8; Self::TAG_BYTE_LEN], data: &mut [u8]) {
+        let mut tag_history = self.kravatte.clone();
+        tag_history.compress_with_suffix(tag, 0b011 | (self.e << 2), 3);
+
+        let mut keystream = vec![0u8; data.len()];
+        tag_history.expand(0, &mut keystream);
+        data.iter_mut().zip(&keystream).for_each(|(byte, &k)| *byte ^= k);
+    }
+}
@@ -8,7 +8,7 @@
 //!
 //! ```toml
 //! [dependencies]
-//! turboshake = "=0.5.0"
+//! turboshake = "=0.6.0"
 //! ```
 //!
 //! Then, use it in your code:
@@ -35,22 +35,44 @@
 //!
 //! See project README @ <https://github.com/itzmeanjan/turboshake> for more details.
 
-#[cfg(feature = "dev")]
+pub mod hash2field;
 pub mod keccak;
-#[cfg(not(feature = "dev"))]
-mod keccak;
+pub mod pq_sampling;
+pub mod sampling;
 
 #[cfg(feature = "dev")]
 pub mod sponge;
 #[cfg(not(feature = "dev"))]
 mod sponge;
 
+#[cfg(any(target_pointer_width = "32", test))]
+mod bit_interleaved;
 mod branch_opt_util;
+mod cyclist;
 mod error;
+mod kdf;
+mod ketje;
+mod keyak;
+mod kravatte;
+mod masked_turboshake128;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod simd128;
+mod spongewrap;
+mod strobe;
 mod tests;
+mod transcript;
 mod turboshake128;
 mod turboshake256;
 
+pub use cyclist::Cyclist;
 pub use error::TurboShakeError;
-pub use turboshake128::TurboShake128;
-pub use turboshake256::TurboShake256;
+pub use kdf::TurboKdf;
+pub use ketje::KetjeMajor;
+pub use keyak::LakeKeyak;
+pub use kravatte::{Kravatte, KravatteSane, KravatteSanse};
+pub use masked_turboshake128::MaskedTurboShake128;
+pub use spongewrap::SpongeWrap;
+pub use strobe::{Strobe, Strobe128, Strobe256};
+pub use transcript::{Transcript, TranscriptRng, TranscriptRngBuilder};
+pub use turboshake128::{TurboShake128, TurboShake128Checkpoint, TurboShake128Prefix, TurboShake128Reader, turboshake128_const};
+pub use turboshake256::{TurboShake256, TurboShake256Checkpoint, TurboShake256Prefix, TurboShake256Reader, turboshake256_const};
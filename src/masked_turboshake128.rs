@@ -0,0 +1,186 @@
+use crate::{
+    TurboShakeError, branch_opt_util, keccak,
+    sampling::{self, XofReader},
+};
+
+/// TurboSHAKE128 Extendable Output Function (XOF), on a Boolean masked sponge state, split into `SHARES` shares, which is
+/// permuted using `keccak::permute_masked`, for absorbing long-term keys on devices exposed to power analysis. Its output is
+/// same as of `TurboShake128`, while it's many times slower, so it's meant to be opted in only for keyed use.
+///
+/// The state starts as a random sharing of zero. Secret input, say a long-term key, must be absorbed using `absorb_shares`,
+/// already split into `SHARES` shares, whose XOR is the input, s.t. share i is added to share i of the state and the input
+/// is never present unmasked. Splitting it is left to the caller, who should keep the key stored that way. Public input,
+/// say a label or a nonce, can be absorbed using `absorb`, which adds it unmasked to first share. Squeezed output is
+/// unmasked, by XOR-ing all shares. Fresh randomness, needed for masking, is squeezed from `rng`, which must be a
+/// cryptographically secure random number generator, seeded with fresh randomness.
+///
+/// # Example
+///
+/// ```
+/// use turboshake::{MaskedTurboShake128, TurboShake128};
+///
+/// // Seed it with fresh randomness, say sampled from operating system's random number generator, never a constant.
+/// let mut seeder = TurboShake128::default();
+/// seeder.absorb(&[0x2a; 32]).expect("must absorb data");
+/// let rng = seeder.fork_finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>().expect("must fork");
+///
+/// // Secret key, stored as two shares, whose XOR is the key.
+/// let key_share0 = [0x5a; 16];
+/// let key_share1 = [0xa5; 16];
+///
+/// let mut ts = MaskedTurboShake128::<_, 2>::new(rng);
+/// assert_eq!(ts.absorb_shares([&key_share0, &key_share1]), Ok(()));
+/// assert_eq!(ts.absorb(b"public label"), Ok(()));
+/// assert_eq!(ts.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+///
+/// let mut output = [0u8; 32];
+/// assert_eq!(ts.squeeze(&mut output), Ok(()));
+/// ```
+pub struct MaskedTurboShake128<R: XofReader, const SHARES: usize = { keccak::MIN_SHARES }> {
+    state: [[u64; keccak::LANE_CNT]; SHARES],
+    offset: usize,
+    is_ready_to_squeeze: usize,
+    squeezable: usize,
+    rng: R,
+}
+
+impl<R: XofReader, const SHARES: usize> MaskedTurboShake128<R, SHARES> {
+    const BIT_LENGTH_OF_KECCAK_PERMUTATION_STATE: usize = keccak::W * keccak::LANE_CNT;
+    const TARGET_BIT_SECURITY_LEVEL: usize = 128;
+    const CAPACITY_BITS: usize = 2 * Self::TARGET_BIT_SECURITY_LEVEL;
+    const RATE_BITS: usize = Self::BIT_LENGTH_OF_KECCAK_PERMUTATION_STATE - Self::CAPACITY_BITS;
+    const RATE_BYTES: usize = Self::RATE_BITS / u8::BITS as usize;
+
+    /// Creates an instance of masked TurboSHAKE128, whose state is a random sharing of zero, using `rng` as source of fresh
+    /// randomness.
+    pub fn new(mut rng: R) -> Self {
+        const { assert!(SHARES >= keccak::MIN_SHARES && SHARES <= keccak::MAX_SHARES) }
+
+        let mut state = [[0u64; keccak::LANE_CNT]; SHARES];
+        for share_idx in 1..SHARES {
+            let share: [u64; keccak::LANE_CNT] = core::array::from_fn(|_| sampling::u64(&mut rng));
+
+            state[share_idx] = share;
+            state[0].iter_mut().zip(share).for_each(|(lane, r)| *lane ^= r);
+        }
+
+        Self {
+            state,
+            offset: 0,
+            is_ready_to_squeeze: usize::MIN,
+            squeezable: 0,
+            rng,
+        }
+    }
+
+    /// Absorbs public `msg` unmasked into first share of the sponge state, permuting it, whenever a block is filled. Don't use
+    /// it for secret input, which must be absorbed using `absorb_shares`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), TurboShakeError>`: `Ok(())` if the absorption was successful.
+    ///   Returns `Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized)` if the instance has already been finalized.
+    pub fn absorb(&mut self, msg: &[u8]) -> Result<(), TurboShakeError> {
+        if branch_opt_util::unlikely(self.is_ready_to_squeeze == usize::MAX) {
+            return Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized);
+        }
+
+        for &byte in msg {
+            self.add_byte(0, self.offset, byte);
+            self.offset += 1;
+
+            if self.offset == Self::RATE_BYTES {
+                keccak::permute_masked(&mut self.state, || sampling::u64(&mut self.rng));
+                self.offset = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Absorbs secret input, given as `SHARES` equal length shares, whose XOR is the input, adding share i into share i of
+    /// the sponge state, s.t. the input is never unmasked. Sponge state is permuted, whenever a block is filled.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), TurboShakeError>`: `Ok(())` if the absorption was successful.
+    ///   Returns `Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized)` if the instance has already been finalized.
+    ///
+    /// # Panics
+    ///
+    /// If shares are not of same length.
+    pub fn absorb_shares(&mut self, shares: [&[u8]; SHARES]) -> Result<(), TurboShakeError> {
+        assert!(shares.iter().all(|share| share.len() == shares[0].len()), "shares must be of same length");
+
+        if branch_opt_util::unlikely(self.is_ready_to_squeeze == usize::MAX) {
+            return Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized);
+        }
+
+        for byte_idx in 0..shares[0].len() {
+            for (share_idx, share) in shares.iter().enumerate() {
+                self.add_byte(share_idx, self.offset, share[byte_idx]);
+            }
+            self.offset += 1;
+
+            if self.offset == Self::RATE_BYTES {
+                keccak::permute_masked(&mut self.state, || sampling::u64(&mut self.rng));
+                self.offset = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finalizes the sponge state, using domain separator `D`, after which it can only be squeezed.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), TurboShakeError>`: `Ok(())` if the finalization was successful.
+    ///   Returns `Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized)` if the instance has already been finalized.
+    pub fn finalize<const D: u8>(&mut self) -> Result<(), TurboShakeError> {
+        // See top of page 2 of https://ia.cr/2023/342
+        const { assert!(D >= 0x01 && D <= 0x7f) };
+
+        if branch_opt_util::unlikely(self.is_ready_to_squeeze == usize::MAX) {
+            return Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized);
+        }
+
+        self.add_byte(0, self.offset, D);
+        self.add_byte(0, Self::RATE_BYTES - 1, 0x80);
+        keccak::permute_masked(&mut self.state, || sampling::u64(&mut self.rng));
+
+        self.is_ready_to_squeeze = usize::MAX;
+        self.squeezable = Self::RATE_BYTES;
+        Ok(())
+    }
+
+    /// Squeezes `out.len()` -bytes unmasked output from the sponge state.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), TurboShakeError>`: `Ok(())` if the squeezing was successful.
+    ///   Returns `Err(TurboShakeError::StillInDataAbsorptionPhase)` if the instance has not yet been finalized.
+    pub fn squeeze(&mut self, out: &mut [u8]) -> Result<(), TurboShakeError> {
+        if branch_opt_util::unlikely(self.is_ready_to_squeeze != usize::MAX) {
+            return Err(TurboShakeError::StillInDataAbsorptionPhase);
+        }
+
+        for byte in out.iter_mut() {
+            if self.squeezable == 0 {
+                keccak::permute_masked(&mut self.state, || sampling::u64(&mut self.rng));
+                self.squeezable = Self::RATE_BYTES;
+            }
+
+            let idx = Self::RATE_BYTES - self.squeezable;
+            *byte = self.state.iter().fold(0u8, |acc, share| acc ^ (share[idx / 8] >> (8 * (idx % 8))) as u8);
+            self.squeezable -= 1;
+        }
+
+        Ok(())
+    }
+
+    /// Adds `byte` at byte offset `idx` of share `share_idx` of the sponge state.
+    fn add_byte(&mut self, share_idx: usize, idx: usize, byte: u8) {
+        self.state[share_idx][idx / 8] ^= (byte as u64) << (8 * (idx % 8));
+    }
+}
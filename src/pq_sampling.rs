@@ -0,0 +1,177 @@
+//! Sampling routines of lattice-based post-quantum schemes ML-KEM and ML-DSA, parameterized by the extendable output function,
+//! s.t. they can be driven by TurboSHAKE{128, 256} readers, for prototyping TurboSHAKE-instantiated variants of those schemes.
+//! Driven by SHAKE{128, 256}, with same input, they produce same output as specified in FIPS 203 and FIPS 204.
+//!
+//! - Uniform polynomials mod q = 3329, following algorithm 7 of ML-KEM https://doi.org/10.6028/NIST.FIPS.203.
+//! - Centered binomial distributed polynomials, following algorithm 8 of ML-KEM.
+//! - Uniform polynomials mod q = 8380417, following algorithm 30 of ML-DSA https://doi.org/10.6028/NIST.FIPS.204.
+//! - Polynomials with coefficients in [-η, η], following algorithm 31 of ML-DSA.
+//! - Polynomials with τ coefficients in {-1, 1} and rest being 0, following algorithm 29 of ML-DSA.
+//!
+//! Uniform polynomials are sampled by squeezing output in chunks of 168 -bytes, which is rate of both SHAKE128 and TurboSHAKE128,
+//! while others squeeze exactly as many bytes as they consume.
+//!
+//! ```
+//! use turboshake::{TurboShake128, pq_sampling};
+//!
+//! let rho = [0u8; 32];
+//! let (j, i) = (0u8, 1u8);
+//!
+//! let mut xof = TurboShake128::default();
+//! xof.absorb(&rho).expect("must absorb data");
+//! xof.absorb(&[j, i]).expect("must absorb data");
+//!
+//! let mut reader = xof.fork_finalize::<{TurboShake128::DEFAULT_DOMAIN_SEPARATOR}>().expect("must fork");
+//! let a_ij = pq_sampling::uniform_poly_mod_3329(&mut reader);
+//! ```
+
+use crate::sampling::XofReader;
+
+/// \# -of coefficients of a polynomial, in both ML-KEM and ML-DSA.
+pub const N: usize = 256;
+
+/// Prime modulus of ML-KEM.
+pub const MLKEM_Q: u32 = 3329;
+
+/// Prime modulus of ML-DSA.
+pub const MLDSA_Q: u32 = 8380417;
+
+/// \# -of bytes squeezed at a time, when sampling uniform polynomials, which is a multiple of 3 and rate of SHAKE128.
+const UNIFORM_SQUEEZE_BYTE_LEN: usize = 168;
+
+/// Samples a polynomial with coefficients uniformly distributed in [0, 3329), using rejection sampling, where each 3 -bytes
+/// of output are interpreted as two 12 -bit candidate coefficients. It's `SampleNTT` i.e. algorithm 7 of FIPS 203, when
+/// `reader` is SHAKE128 of 34 -bytes input ρ || j || i.
+pub fn uniform_poly_mod_3329<R: XofReader>(reader: &mut R) -> [u32; N] {
+    let mut poly = [0u32; N];
+    let mut buf = [0u8; UNIFORM_SQUEEZE_BYTE_LEN];
+
+    let mut j = 0;
+    while j < N {
+        reader.squeeze(&mut buf);
+
+        for chunk in buf.chunks_exact(3) {
+            let d1 = chunk[0] as u32 | ((chunk[1] as u32 & 0x0f) << 8);
+            let d2 = (chunk[1] as u32 >> 4) | ((chunk[2] as u32) << 4);
+
+            if d1 < MLKEM_Q && j < N {
+                poly[j] = d1;
+                j += 1;
+            }
+            if d2 < MLKEM_Q && j < N {
+                poly[j] = d2;
+                j += 1;
+            }
+        }
+    }
+
+    poly
+}
+
+/// Samples a polynomial with coefficients uniformly distributed in [0, 8380417), using rejection sampling, where each 3 -bytes
+/// of output are interpreted as a little-endian 23 -bit candidate coefficient, ignoring top bit. It's `RejNTTPoly` i.e.
+/// algorithm 30 of FIPS 204, when `reader` is SHAKE128 of 34 -bytes input ρ || s || r.
+pub fn uniform_poly_mod_8380417<R: XofReader>(reader: &mut R) -> [u32; N] {
+    let mut poly = [0u32; N];
+    let mut buf = [0u8; UNIFORM_SQUEEZE_BYTE_LEN];
+
+    let mut j = 0;
+    while j < N {
+        reader.squeeze(&mut buf);
+
+        for chunk in buf.chunks_exact(3) {
+            let z = chunk[0] as u32 | ((chunk[1] as u32) << 8) | ((chunk[2] as u32 & 0x7f) << 16);
+
+            if z < MLDSA_Q && j < N {
+                poly[j] = z;
+                j += 1;
+            }
+        }
+    }
+
+    poly
+}
+
+/// Samples a polynomial with coefficients in [-η, η], following centered binomial distribution, for η ∈ {2, 3}. It squeezes
+/// 64 · η -bytes, where each coefficient is computed as difference of sums of two consecutive groups of η bits. It's
+/// `SamplePolyCBD` i.e. algorithm 8 of FIPS 203, with coefficients centered around 0, when `reader` is SHAKE256 of 33 -bytes
+/// input σ || N i.e. `PRF`.
+pub fn cbd_poly<const ETA: usize, R: XofReader>(reader: &mut R) -> [i32; N] {
+    const { assert!(ETA == 2 || ETA == 3) }
+
+    let mut buf = [0u8; 64 * 3];
+    let bytes = &mut buf[..64 * ETA];
+    reader.squeeze(bytes);
+
+    let bit = |idx: usize| ((bytes[idx / 8] >> (idx % 8)) & 1) as i32;
+
+    core::array::from_fn(|i| {
+        let x: i32 = (0..ETA).map(|j| bit(2 * i * ETA + j)).sum();
+        let y: i32 = (0..ETA).map(|j| bit(2 * i * ETA + ETA + j)).sum();
+
+        x - y
+    })
+}
+
+/// Samples a polynomial with coefficients uniformly distributed in [-η, η], for η ∈ {2, 4}, using rejection sampling, where
+/// each byte of output is interpreted as two 4 -bit candidates, lower one first. For η = 2, a candidate b < 15 gives 2 - (b mod 5),
+/// while for η = 4, a candidate b < 9 gives 4 - b. It's `RejBoundedPoly` i.e. algorithm 31 of FIPS 204, when `reader` is
+/// SHAKE256 of 66 -bytes input ρ' || r.
+pub fn bounded_poly<const ETA: u8, R: XofReader>(reader: &mut R) -> [i32; N] {
+    const { assert!(ETA == 2 || ETA == 4) }
+
+    let coeff_from_half_byte = |b: u8| match ETA {
+        2 if b < 15 => Some(2 - (b % 5) as i32),
+        4 if b < 9 => Some(4 - b as i32),
+        _ => None,
+    };
+
+    let mut poly = [0i32; N];
+    let mut byte = [0u8; 1];
+
+    let mut j = 0;
+    while j < N {
+        reader.squeeze(&mut byte);
+
+        if let Some(z0) = coeff_from_half_byte(byte[0] & 0x0f) {
+            poly[j] = z0;
+            j += 1;
+        }
+        if let Some(z1) = coeff_from_half_byte(byte[0] >> 4) {
+            if j < N {
+                poly[j] = z1;
+                j += 1;
+            }
+        }
+    }
+
+    poly
+}
+
+/// Samples a polynomial with exactly τ coefficients in {-1, 1} and rest being 0, for 0 < τ <= 64. It squeezes 8 -bytes for
+/// signs of non-zero coefficients, followed by a byte at a time, for rejection sampling positions, using an inside-out
+/// Fisher-Yates shuffle. It's `SampleInBall` i.e. algorithm 29 of FIPS 204, when `reader` is SHAKE256 of commitment hash.
+pub fn sample_in_ball<const TAU: usize, R: XofReader>(reader: &mut R) -> [i32; N] {
+    const { assert!(TAU > 0 && TAU <= 64) }
+
+    let mut signs = [0u8; 8];
+    reader.squeeze(&mut signs);
+    let signs = u64::from_le_bytes(signs);
+
+    let mut poly = [0i32; N];
+    let mut byte = [0u8; 1];
+
+    for i in (N - TAU)..N {
+        let j = loop {
+            reader.squeeze(&mut byte);
+            if byte[0] as usize <= i {
+                break byte[0] as usize;
+            }
+        };
+
+        poly[i] = poly[j];
+        poly[j] = 1 - 2 * ((signs >> (i + TAU - N)) & 1) as i32;
+    }
+
+    poly
+}
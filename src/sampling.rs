@@ -0,0 +1,149 @@
+//! Uniform sampling on top of TurboSHAKE{128, 256} output, using rejection sampling, s.t. sampled values are unbiased. Any
+//! squeezing TurboSHAKE{128, 256} instance can be sampled from, be it a reader or a finalized hasher.
+//!
+//! Each routine consumes output bytes in a documented way, interpreting them as little-endian integers, without depending on
+//! pointer width or endianness of the target. So, given same reader, sampled values are same on every target, which makes
+//! them usable for reproducible simulations.
+//!
+//! ```
+//! use turboshake::{TurboShake128, sampling};
+//!
+//! let mut hasher = TurboShake128::default();
+//! hasher.absorb(b"simulation seed").expect("must absorb data");
+//!
+//! let mut reader = hasher.fork_finalize::<{TurboShake128::DEFAULT_DOMAIN_SEPARATOR}>().expect("must fork");
+//!
+//! let die = sampling::u64_below(&mut reader, 6) + 1;
+//! let probability = sampling::f64_unit(&mut reader);
+//!
+//! let mut deck: Vec<u8> = (0..52).collect();
+//! sampling::shuffle(&mut reader, &mut deck);
+//!
+//! let mut bloom_filter_bits = [0usize; 7];
+//! sampling::distinct_indices(&mut reader, 1 << 20, &mut bloom_filter_bits);
+//! ```
+
+use crate::{TurboShake128, TurboShake128Reader, TurboShake256, TurboShake256Reader};
+
+/// Reader of an extendable output function's output, which can be used as source of bytes, for sampling.
+pub trait XofReader {
+    /// Squeezes next `out.len()` -bytes of output.
+    fn squeeze(&mut self, out: &mut [u8]);
+}
+
+impl XofReader for TurboShake128Reader {
+    fn squeeze(&mut self, out: &mut [u8]) {
+        TurboShake128Reader::squeeze(self, out);
+    }
+}
+
+impl XofReader for TurboShake256Reader {
+    fn squeeze(&mut self, out: &mut [u8]) {
+        TurboShake256Reader::squeeze(self, out);
+    }
+}
+
+/// Finalized TurboSHAKE128 instance can be sampled from, same as its reader.
+///
+/// # Panics
+///
+/// If the instance has not yet been finalized.
+impl XofReader for TurboShake128 {
+    fn squeeze(&mut self, out: &mut [u8]) {
+        TurboShake128::squeeze(self, out).expect("TurboSHAKE128 instance must be finalized, before sampling from it");
+    }
+}
+
+/// Finalized TurboSHAKE256 instance can be sampled from, same as its reader.
+///
+/// # Panics
+///
+/// If the instance has not yet been finalized.
+impl XofReader for TurboShake256 {
+    fn squeeze(&mut self, out: &mut [u8]) {
+        TurboShake256::squeeze(self, out).expect("TurboSHAKE256 instance must be finalized, before sampling from it");
+    }
+}
+
+/// Samples a uniform random `u64`, by squeezing 8 -bytes and interpreting them as a little-endian integer.
+pub fn u64<R: XofReader>(reader: &mut R) -> u64 {
+    let mut bytes = [0u8; 8];
+    reader.squeeze(&mut bytes);
+
+    u64::from_le_bytes(bytes)
+}
+
+/// Samples a uniform random integer in [0, n), using rejection sampling. Let b be bit length of n - 1 and k = ⌈b / 8⌉.
+/// Each attempt squeezes k -bytes, interprets them as a little-endian integer, keeping only its lowest b bits. The attempt
+/// succeeds if the result is < n, which happens with probability > 1/2. When n = 1, it returns 0, consuming no bytes.
+///
+/// # Panics
+///
+/// If n = 0.
+pub fn u64_below<R: XofReader>(reader: &mut R, n: u64) -> u64 {
+    assert!(n > 0, "upper bound must be non-zero");
+
+    let num_bits = u64::BITS - (n - 1).leading_zeros();
+    if num_bits == 0 {
+        return 0;
+    }
+
+    let num_bytes = num_bits.div_ceil(u8::BITS) as usize;
+    let mask = u64::MAX >> (u64::BITS - num_bits);
+
+    let mut bytes = [0u8; 8];
+    loop {
+        reader.squeeze(&mut bytes[..num_bytes]);
+
+        let value = u64::from_le_bytes(bytes) & mask;
+        if value < n {
+            return value;
+        }
+    }
+}
+
+/// Samples a uniform random index in [0, n), consuming output bytes exactly like `u64_below`, so that it's portable
+/// across targets of different pointer width.
+///
+/// # Panics
+///
+/// If n = 0.
+pub fn usize_below<R: XofReader>(reader: &mut R, n: usize) -> usize {
+    u64_below(reader, n as u64) as usize
+}
+
+/// Samples a uniform random `f64` in [0, 1), by squeezing 8 -bytes, interpreting them as a little-endian integer and
+/// scaling its top 53 bits by 2^-53, s.t. each of 2^53 equally spaced values is equally likely.
+pub fn f64_unit<R: XofReader>(reader: &mut R) -> f64 {
+    const SCALE: f64 = 1.0 / (1u64 << f64::MANTISSA_DIGITS) as f64;
+    (u64(reader) >> (u64::BITS - f64::MANTISSA_DIGITS)) as f64 * SCALE
+}
+
+/// Shuffles `items` uniformly at random, using Fisher-Yates shuffle. For i = len - 1 down to 1, it samples j in [0, i]
+/// using `usize_below` and swaps items at index i and j.
+pub fn shuffle<R: XofReader, T>(reader: &mut R, items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+        let j = usize_below(reader, i + 1);
+        items.swap(i, j);
+    }
+}
+
+/// Fills `indices` with distinct uniform random indices in [0, n), say for setting bits of a Bloom filter. Indices are
+/// sampled in order, using `usize_below`, resampling an index when it's already sampled. It's meant for a small number of
+/// indices, as it compares each of them with previously sampled ones.
+///
+/// # Panics
+///
+/// If `indices.len()` > n.
+pub fn distinct_indices<R: XofReader>(reader: &mut R, n: usize, indices: &mut [usize]) {
+    assert!(indices.len() <= n, "can't sample more distinct indices than upper bound");
+
+    for i in 0..indices.len() {
+        indices[i] = loop {
+            let index = usize_below(reader, n);
+            if !indices[..i].contains(&index) {
+                break index;
+            }
+        };
+    }
+}
@@ -0,0 +1,46 @@
+//! WebAssembly SIMD128 backend of Keccak-p\[1600, 12\] permutation, which permutes two states at once, keeping
+//! same lane of both states in a single 128 -bit vector register. Selected when compiling for `wasm32` target, with
+//! `simd128` target feature enabled.
+
+use crate::keccak;
+use core::arch::wasm32::{i64x2_shl, u64x2, u64x2_extract_lane, u64x2_shr, u64x2_splat, v128, v128_andnot, v128_or, v128_xor};
+
+/// Same lane of two Keccak-p\[1600, 12\] permutation states, packed into a 128 -bit vector.
+#[derive(Clone, Copy)]
+struct Lanex2(v128);
+
+impl keccak::Lane for Lanex2 {
+    const L: usize = 6;
+    const ZERO: Self = Lanex2(u64x2(0, 0));
+
+    #[inline(always)]
+    fn xor(self, rhs: Self) -> Self {
+        Lanex2(v128_xor(self.0, rhs.0))
+    }
+
+    #[inline(always)]
+    fn andn(self, rhs: Self) -> Self {
+        Lanex2(v128_andnot(rhs.0, self.0))
+    }
+
+    #[inline(always)]
+    fn rotl(self, n: u32) -> Self {
+        Lanex2(v128_or(i64x2_shl(self.0, n), u64x2_shr(self.0, 64 - n)))
+    }
+
+    #[inline(always)]
+    fn from_rc(rc: u64) -> Self {
+        Lanex2(u64x2_splat(rc))
+    }
+}
+
+/// Keccak-p\[1600, 12\] permutation, applied on two states at once, where `state[i][j]` is lane i of state j.
+pub fn permute_x2(state: &mut [[u64; 2]; keccak::LANE_CNT]) {
+    let mut lanes: [Lanex2; keccak::LANE_CNT] = core::array::from_fn(|i| Lanex2(u64x2(state[i][0], state[i][1])));
+    keccak::permute_p::<Lanex2, { keccak::ROUNDS }>(&mut lanes);
+
+    state
+        .iter_mut()
+        .zip(lanes)
+        .for_each(|(dst, lane)| *dst = [u64x2_extract_lane::<0>(lane.0), u64x2_extract_lane::<1>(lane.0)]);
+}
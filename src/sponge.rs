@@ -1,8 +1,53 @@
 use crate::{branch_opt_util, keccak};
 use std::cmp::min;
 
+#[cfg(target_pointer_width = "32")]
+use crate::bit_interleaved;
+
 const KECCAK_WORD_BYTE_LEN: usize = keccak::W / u8::BITS as usize;
 
+/// Converts a little-endian 64 -bit word, read from message, into representation of lanes, kept in sponge state.
+/// On 32 -bit targets, sponge state is kept bit-interleaved, so that bit-interleaved permutation can be used.
+#[inline(always)]
+fn to_state_word(word: u64) -> u64 {
+    #[cfg(target_pointer_width = "32")]
+    return bit_interleaved::interleave(word);
+
+    #[cfg(not(target_pointer_width = "32"))]
+    return word;
+}
+
+/// Converts a lane of sponge state back into a little-endian 64 -bit word, which can be squeezed out.
+#[inline(always)]
+fn from_state_word(word: u64) -> u64 {
+    #[cfg(target_pointer_width = "32")]
+    return bit_interleaved::deinterleave(word);
+
+    #[cfg(not(target_pointer_width = "32"))]
+    return word;
+}
+
+/// Applies Keccak-p\[1600, 12\] permutation on sponge state, using bit-interleaved implementation on 32 -bit targets.
+#[inline(always)]
+fn permute(state: &mut [u64; keccak::LANE_CNT]) {
+    #[cfg(target_pointer_width = "32")]
+    bit_interleaved::permute(state);
+
+    #[cfg(not(target_pointer_width = "32"))]
+    keccak::permute(state);
+}
+
+/// Applies Keccak-p\[1600, 12\] permutation on sponge state, s.t. only first `NUM_OUT_ROWS` rows of five lanes are guaranteed
+/// to be correctly computed, by truncating last round. Targets and features using other permutation backends compute all lanes.
+#[inline(always)]
+fn permute_truncated<const NUM_OUT_ROWS: usize>(state: &mut [u64; keccak::LANE_CNT]) {
+    #[cfg(any(feature = "small", feature = "lane-complementing", target_pointer_width = "32"))]
+    permute(state);
+
+    #[cfg(not(any(feature = "small", feature = "lane-complementing", target_pointer_width = "32")))]
+    keccak::permute_truncated::<NUM_OUT_ROWS>(state);
+}
+
 /// Given N -bytes message, this routine consumes it into Keccak\[c\] permutation state s.t.
 /// `offset` ( second parameter ) denotes how many bytes are already consumed into rate portion
 /// of the state.
@@ -33,7 +78,7 @@ pub fn absorb<const NUM_BYTES_IN_RATE: usize>(state: &mut [u64; keccak::LANE_CNT
             .for_each(|chunk_bytes| {
                 let chunk_as_word = u64::from_le_bytes(chunk_bytes.try_into().unwrap());
 
-                state[state_word_index] ^= chunk_as_word;
+                state[state_word_index] ^= to_state_word(chunk_as_word);
                 state_word_index += 1;
             });
 
@@ -41,7 +86,7 @@ pub fn absorb<const NUM_BYTES_IN_RATE: usize>(state: &mut [u64; keccak::LANE_CNT
         msg_offset += absorbable_num_bytes;
 
         if branch_opt_util::unlikely(*offset == NUM_BYTES_IN_RATE) {
-            keccak::permute(state);
+            permute(state);
             *offset = 0;
         }
     }
@@ -61,13 +106,54 @@ pub fn finalize<const NUM_BYTES_IN_RATE: usize, const D: u8>(state: &mut [u64; k
     let byte_index_in_state_word = *offset % KECCAK_WORD_BYTE_LEN;
     let shl_bit_offset = byte_index_in_state_word * u8::BITS as usize;
 
-    state[state_word_index] ^= (D as u64) << shl_bit_offset;
-    state[num_words_in_rate - 1] ^= 0x80u64 << (keccak::W - u8::BITS as usize);
+    state[state_word_index] ^= to_state_word((D as u64) << shl_bit_offset);
+    state[num_words_in_rate - 1] ^= to_state_word(0x80u64 << (keccak::W - u8::BITS as usize));
 
-    keccak::permute(state);
+    permute(state);
     *offset = 0;
 }
 
+/// Given that N message bytes are already consumed into Keccak\[c\] permutation state, this routine finalizes sponge
+/// state and squeezes `out` from it, for one-shot hashing, where sponge state is not used anymore. When `out` is at most
+/// 64 -bytes, it computes only lanes to be squeezed, in the last round of the permutation, otherwise it's same as calling
+/// `finalize` and `squeeze`.
+///
+/// - c i.e. capacity can be either of 256 or 512 -bits.
+/// - Rate portion will have bitwidth of 1600 - c.
+/// - `offset` will live in 0 <= offset < RATE_BYTES.
+#[inline(always)]
+pub fn finalize_and_squeeze_short<const NUM_BYTES_IN_RATE: usize, const D: u8>(state: &mut [u64; keccak::LANE_CNT], offset: &mut usize, out: &mut [u8]) {
+    const SHORT_OUT_BYTE_LEN: usize = 64;
+
+    if out.len() > SHORT_OUT_BYTE_LEN {
+        finalize::<NUM_BYTES_IN_RATE, D>(state, offset);
+
+        let mut readable = NUM_BYTES_IN_RATE;
+        squeeze::<NUM_BYTES_IN_RATE>(state, &mut readable, out);
+        return;
+    }
+
+    let num_words_in_rate = const { NUM_BYTES_IN_RATE / u8::BITS as usize };
+    let state_word_index = *offset / KECCAK_WORD_BYTE_LEN;
+    let byte_index_in_state_word = *offset % KECCAK_WORD_BYTE_LEN;
+    let shl_bit_offset = byte_index_in_state_word * u8::BITS as usize;
+
+    state[state_word_index] ^= to_state_word((D as u64) << shl_bit_offset);
+    state[num_words_in_rate - 1] ^= to_state_word(0x80u64 << (keccak::W - u8::BITS as usize));
+
+    // Each row of the state holds 40 -bytes, so at most two rows are to be squeezed.
+    if out.len() <= 5 * KECCAK_WORD_BYTE_LEN {
+        permute_truncated::<1>(state);
+    } else {
+        permute_truncated::<2>(state);
+    }
+    *offset = 0;
+
+    out.chunks_mut(KECCAK_WORD_BYTE_LEN).zip(state.iter()).for_each(|(chunk_bytes, &word)| {
+        chunk_bytes.copy_from_slice(&from_state_word(word).to_le_bytes()[..chunk_bytes.len()]);
+    });
+}
+
 /// Given that Keccak\[c\] permutation state is finalized, this routine can be invoked
 /// for squeezing N -bytes out of rate portion of the state.
 ///
@@ -94,7 +180,7 @@ pub fn squeeze<const NUM_BYTES_IN_RATE: usize>(state: &mut [u64; keccak::LANE_CN
         block[padded_effective_block_begins_at..padded_efffective_block_byte_len]
             .chunks_exact_mut(KECCAK_WORD_BYTE_LEN)
             .for_each(|chunk_bytes| {
-                chunk_bytes.copy_from_slice(&state[state_word_index].to_le_bytes());
+                chunk_bytes.copy_from_slice(&from_state_word(state[state_word_index]).to_le_bytes());
                 state_word_index += 1;
             });
 
@@ -104,8 +190,123 @@ pub fn squeeze<const NUM_BYTES_IN_RATE: usize>(state: &mut [u64; keccak::LANE_CN
         out_offset += squeezable_num_bytes;
 
         if branch_opt_util::unlikely(*readable == 0) {
-            keccak::permute(state);
+            permute(state);
             *readable = NUM_BYTES_IN_RATE;
         }
     }
 }
+
+/// Given that Keccak\[c\] permutation state is finalized, this routine can be invoked for skipping
+/// N -bytes of output, as if they were squeezed and discarded, without copying them out of the state.
+/// Whole blocks are skipped by only permutating the state.
+///
+/// - c i.e. capacity can be either of 256 or 512 -bits.
+/// - Rate portion will have bitwidth of 1600 - c.
+/// - `readable` denotes how many bytes can be squeezed without permutating the sponge state.
+#[inline(always)]
+pub fn skip<const NUM_BYTES_IN_RATE: usize>(state: &mut [u64; keccak::LANE_CNT], readable: &mut usize, num_bytes: u64) {
+    if num_bytes < *readable as u64 {
+        *readable -= num_bytes as usize;
+        return;
+    }
+
+    let remaining_num_bytes = num_bytes - *readable as u64;
+    let num_blocks = remaining_num_bytes / NUM_BYTES_IN_RATE as u64;
+
+    for _ in 0..=num_blocks {
+        permute(state);
+    }
+
+    *readable = NUM_BYTES_IN_RATE - (remaining_num_bytes % NUM_BYTES_IN_RATE as u64) as usize;
+}
+
+/// Compile-time evaluable one-shot sponge, which absorbs N -bytes message into a fresh Keccak\[c\] permutation state,
+/// finalizes it with domain separator D and squeezes `OUT_BYTE_LEN` -bytes out of it. It processes one byte at a time,
+/// so it's much slower than `absorb`, `finalize` and `squeeze`, but it can be used for computing constants at compile-time.
+///
+/// - c i.e. capacity can be either of 256 or 512 -bits.
+/// - Rate portion will have bitwidth of 1600 - c.
+pub const fn oneshot<const NUM_BYTES_IN_RATE: usize, const D: u8, const OUT_BYTE_LEN: usize>(msg: &[u8]) -> [u8; OUT_BYTE_LEN] {
+    const { assert!(NUM_BYTES_IN_RATE % KECCAK_WORD_BYTE_LEN == 0) }
+
+    let mut state = [0u64; keccak::LANE_CNT];
+
+    let mut offset = 0;
+    let mut msg_offset = 0;
+    while msg_offset < msg.len() {
+        state[offset / KECCAK_WORD_BYTE_LEN] ^= (msg[msg_offset] as u64) << ((offset % KECCAK_WORD_BYTE_LEN) * u8::BITS as usize);
+
+        offset += 1;
+        msg_offset += 1;
+
+        if offset == NUM_BYTES_IN_RATE {
+            keccak::permute(&mut state);
+            offset = 0;
+        }
+    }
+
+    state[offset / KECCAK_WORD_BYTE_LEN] ^= (D as u64) << ((offset % KECCAK_WORD_BYTE_LEN) * u8::BITS as usize);
+    state[(NUM_BYTES_IN_RATE / KECCAK_WORD_BYTE_LEN) - 1] ^= 0x80u64 << (keccak::W - u8::BITS as usize);
+    keccak::permute(&mut state);
+
+    let mut out = [0u8; OUT_BYTE_LEN];
+
+    let mut readable = NUM_BYTES_IN_RATE;
+    let mut out_offset = 0;
+    while out_offset < OUT_BYTE_LEN {
+        let state_byte_offset = NUM_BYTES_IN_RATE - readable;
+        out[out_offset] = (state[state_byte_offset / KECCAK_WORD_BYTE_LEN] >> ((state_byte_offset % KECCAK_WORD_BYTE_LEN) * u8::BITS as usize)) as u8;
+
+        readable -= 1;
+        out_offset += 1;
+
+        if readable == 0 {
+            keccak::permute(&mut state);
+            readable = NUM_BYTES_IN_RATE;
+        }
+    }
+
+    out
+}
+
+/// One-shot sponge over two messages at once, which absorbs each message into a fresh Keccak\[c\] permutation state,
+/// finalizes it with domain separator D and squeezes as many bytes as the corresponding output slice can hold. Full message
+/// blocks, common to both messages, are absorbed in lockstep, using `keccak::permute_x2`, which falls back to portable
+/// `keccak::permute_many`, when WebAssembly SIMD128 backend isn't available, while remaining message bytes are absorbed
+/// separately, for each state. So it's most effective when both messages are of same length, which is the case for leaves
+/// of a K12-style tree hash.
+///
+/// - c i.e. capacity can be either of 256 or 512 -bits.
+/// - Rate portion will have bitwidth of 1600 - c.
+pub fn oneshot_x2<const NUM_BYTES_IN_RATE: usize, const D: u8>(msgs: [&[u8]; 2], outs: [&mut [u8]; 2]) {
+    const { assert!(NUM_BYTES_IN_RATE % KECCAK_WORD_BYTE_LEN == 0) }
+
+    let num_common_blocks = min(msgs[0].len(), msgs[1].len()) / NUM_BYTES_IN_RATE;
+    let num_common_bytes = num_common_blocks * NUM_BYTES_IN_RATE;
+
+    let mut states = [[0u64; 2]; keccak::LANE_CNT];
+
+    for blk_offset in (0..num_common_bytes).step_by(NUM_BYTES_IN_RATE) {
+        for (j, msg) in msgs.iter().enumerate() {
+            msg[blk_offset..(blk_offset + NUM_BYTES_IN_RATE)]
+                .chunks_exact(KECCAK_WORD_BYTE_LEN)
+                .enumerate()
+                .for_each(|(i, chunk_bytes)| {
+                    states[i][j] ^= u64::from_le_bytes(chunk_bytes.try_into().unwrap());
+                });
+        }
+
+        keccak::permute_x2(&mut states);
+    }
+
+    for (j, (msg, out)) in msgs.into_iter().zip(outs).enumerate() {
+        let mut state = [0u64; keccak::LANE_CNT];
+        for i in 0..keccak::LANE_CNT {
+            state[i] = to_state_word(states[i][j]);
+        }
+
+        let mut offset = 0;
+        absorb::<NUM_BYTES_IN_RATE>(&mut state, &mut offset, &msg[num_common_bytes..]);
+        finalize_and_squeeze_short::<NUM_BYTES_IN_RATE, D>(&mut state, &mut offset, out);
+    }
+}
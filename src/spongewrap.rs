@@ -0,0 +1,157 @@
+//! SpongeWrap authenticated encryption, as specified in section 6 of "Duplexing the sponge" https://eprint.iacr.org/2011/499,
+//! on top of the duplex construction, instantiated with Keccak-p\[1600, 12\] permutation and 256 -bits capacity.
+//!
+//! Each duplexing call absorbs a block of at most 167 -bytes, followed by a frame bit and pad10*1 padding, s.t. padded block
+//! fits in 168 -bytes rate. Key and nonce are absorbed as a sequence of blocks, where only last block has frame bit 0. Each
+//! wrap absorbs associated data blocks, where only last block has frame bit 1, followed by plaintext blocks, where only last
+//! block has frame bit 0, while output of each duplexing call is used as keystream for encrypting next plaintext block. Output
+//! of the last duplexing call is the tag.
+
+use crate::{TurboShakeError, keccak};
+
+/// Rate of the duplex object, in bytes.
+const RATE: usize = keccak::STATE_BYTE_LEN - 32;
+
+/// Maximum byte length of a block, absorbed in a duplexing call, leaving room for frame bit and padding.
+const BLOCK_BYTE_LEN: usize = RATE - 1;
+
+/// SpongeWrap object, keyed with a secret key and a nonce, which can wrap/ unwrap many messages in a session, where each tag
+/// authenticates all messages wrapped so far. Both ends of a session must wrap and unwrap same sequence of messages.
+///
+/// ```
+/// use turboshake::SpongeWrap;
+///
+/// let mut sender = SpongeWrap::new(b"secret key", b"unique nonce");
+/// let mut receiver = sender.clone();
+///
+/// let mut msg = *b"plaintext";
+/// let tag = sender.seal(b"associated data", &mut msg);
+///
+/// assert!(receiver.open(b"associated data", &mut msg, &tag).is_ok());
+/// assert_eq!(&msg, b"plaintext");
+/// ```
+#[derive(Clone)]
+pub struct SpongeWrap {
+    state: [u8; keccak::STATE_BYTE_LEN],
+    failed: bool,
+}
+
+impl SpongeWrap {
+    /// Byte length of the authentication tag.
+    pub const TAG_BYTE_LEN: usize = 16;
+
+    /// Creates a SpongeWrap object, absorbing secret `key`, followed by `nonce`. A nonce must never be reused with same key.
+    pub fn new(key: &[u8], nonce: &[u8]) -> Self {
+        let mut spongewrap = Self {
+            state: [0u8; keccak::STATE_BYTE_LEN],
+            failed: false,
+        };
+
+        spongewrap.absorb_framed(key, 1, 0);
+        spongewrap.absorb_framed(nonce, 1, 0);
+        spongewrap
+    }
+
+    /// Encrypts `data` in place, authenticating it along with associated data `ad`, returning the tag.
+    ///
+    /// # Panics
+    ///
+    /// If a previous `open` call of the object has failed.
+    pub fn seal(&mut self, ad: &[u8], data: &mut [u8]) -> [u8; Self::TAG_BYTE_LEN] {
+        assert!(!self.failed, "object must not be used after a failed open");
+        self.wrap(ad, data, false)
+    }
+
+    /// Decrypts `data` in place, verifying the tag over it and associated data `ad`, in constant-time.
+    ///
+    /// # Returns
+    ///
+    /// * Returns `Ok(())` if the tag is valid, leaving plaintext in `data`.
+    ///   Returns Err(TurboShakeError::AuthenticationFailed) if the tag is invalid, zeroing `data`, so that unauthenticated
+    ///   plaintext is never released. The object can't be used anymore, after a failure.
+    ///
+    /// # Panics
+    ///
+    /// If a previous `open` call of the object has failed.
+    pub fn open(&mut self, ad: &[u8], data: &mut [u8], tag: &[u8; Self::TAG_BYTE_LEN]) -> Result<(), TurboShakeError> {
+        assert!(!self.failed, "object must not be used after a failed open");
+        let computed_tag = self.wrap(ad, data, true);
+
+        let diff = computed_tag.iter().zip(tag).fold(0u8, |acc, (&a, &b)| acc | (a ^ b));
+        if diff == 0 {
+            Ok(())
+        } else {
+            data.fill(0);
+            self.failed = true;
+            Err(TurboShakeError::AuthenticationFailed)
+        }
+    }
+
+    /// Absorbs `ad`, followed by encrypting or decrypting `data` in place, returning the tag. As keystream for encrypting a
+    /// block is output of previous duplexing call, i.e. first bytes of the state, a plaintext block is encrypted by adding it
+    /// to the state, which leaves the ciphertext block in the state, while a ciphertext block is decrypted by swapping it in.
+    fn wrap(&mut self, ad: &[u8], data: &mut [u8], decrypt: bool) -> [u8; Self::TAG_BYTE_LEN] {
+        let mut ad_blocks = ad.chunks(BLOCK_BYTE_LEN);
+        let mut ad_block = ad_blocks.next().unwrap_or_default();
+        for next_ad_block in ad_blocks {
+            self.duplexing(ad_block, 0);
+            ad_block = next_ad_block;
+        }
+        self.duplexing(ad_block, 1);
+
+        let mut data_blocks = data.chunks_mut(BLOCK_BYTE_LEN).peekable();
+        if data_blocks.peek().is_none() {
+            self.duplexing(&[], 0);
+        }
+
+        while let Some(block) = data_blocks.next() {
+            if decrypt {
+                self.state.iter_mut().zip(block.iter_mut()).for_each(|(s, byte)| {
+                    let ct = *byte;
+                    *byte ^= *s;
+                    *s = ct;
+                });
+            } else {
+                self.state.iter_mut().zip(block.iter_mut()).for_each(|(s, byte)| {
+                    *s ^= *byte;
+                    *byte = *s;
+                });
+            }
+
+            let frame_bit = if data_blocks.peek().is_some() { 1 } else { 0 };
+            self.pad_and_permute(block.len(), frame_bit);
+        }
+
+        let mut tag = [0u8; Self::TAG_BYTE_LEN];
+        tag.copy_from_slice(&self.state[..Self::TAG_BYTE_LEN]);
+        tag
+    }
+
+    /// Absorbs `data` as a sequence of blocks, where last block has frame bit `last_frame_bit` and others have `frame_bit`.
+    /// Empty `data` is absorbed as a single empty block.
+    fn absorb_framed(&mut self, data: &[u8], frame_bit: u8, last_frame_bit: u8) {
+        let mut blocks = data.chunks(BLOCK_BYTE_LEN);
+        let mut block = blocks.next().unwrap_or_default();
+
+        for next_block in blocks {
+            self.duplexing(block, frame_bit);
+            block = next_block;
+        }
+        self.duplexing(block, last_frame_bit);
+    }
+
+    /// Absorbs `block`, followed by `frame_bit` and pad10*1 padding, and permutes the state, whose first bytes are output of
+    /// the duplexing call.
+    fn duplexing(&mut self, block: &[u8], frame_bit: u8) {
+        self.state.iter_mut().zip(block).for_each(|(s, &b)| *s ^= b);
+        self.pad_and_permute(block.len(), frame_bit);
+    }
+
+    /// Appends `frame_bit` and pad10*1 padding to a block of `block_len` -bytes, already added to the state, and permutes it.
+    fn pad_and_permute(&mut self, block_len: usize, frame_bit: u8) {
+        self.state[block_len] ^= frame_bit | 0x02;
+        self.state[RATE - 1] ^= 0x80;
+
+        keccak::permute_nr_bytes::<{ keccak::ROUNDS }>(&mut self.state);
+    }
+}
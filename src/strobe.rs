@@ -0,0 +1,329 @@
+//! STROBE protocol framework https://strobe.sourceforge.io/specs, on top of Keccak-p\[1600, NUM_ROUNDS\] permutation, which
+//! is Keccak-p\[1600, 12\] by default. Instantiated with 24 rounds, it's STROBE v1.0.2, which uses Keccak-f\[1600\].
+
+use crate::{TurboShakeError, keccak};
+
+/// Flag bits of STROBE operations.
+const FLAG_I: u8 = 1;
+const FLAG_A: u8 = 1 << 1;
+const FLAG_C: u8 = 1 << 2;
+const FLAG_T: u8 = 1 << 3;
+const FLAG_M: u8 = 1 << 4;
+const FLAG_K: u8 = 1 << 5;
+
+/// STROBE instance at 128 -bit security level, with rate of 166 -bytes, on top of Keccak-p\[1600, 12\].
+pub type Strobe128 = Strobe<128>;
+
+/// STROBE instance at 256 -bit security level, with rate of 134 -bytes, on top of Keccak-p\[1600, 12\].
+pub type Strobe256 = Strobe<256>;
+
+/// STROBE instance at security level of `SECURITY_BITS`, which is either 128 or 256, on top of Keccak-p\[1600, NUM_ROUNDS\].
+///
+/// Each operation takes a `more` flag, which, when set, continues previous operation, s.t. data can be processed in a
+/// streaming manner. Each operation also has a `meta_` variant, which sets M flag, for framing data.
+///
+/// ```
+/// use turboshake::Strobe128;
+///
+/// let mut alice = Strobe128::new(b"my protocol");
+/// let mut bob = Strobe128::new(b"my protocol");
+///
+/// alice.key(b"shared secret key", false);
+/// bob.key(b"shared secret key", false);
+///
+/// let mut msg = *b"hello bob";
+/// alice.send_enc(&mut msg, false);
+/// bob.recv_enc(&mut msg, false);
+/// assert_eq!(&msg, b"hello bob");
+///
+/// let mut tag = [0u8; 16];
+/// alice.send_mac(&mut tag, false);
+/// assert!(bob.recv_mac(&mut tag).is_ok());
+/// ```
+#[derive(Clone)]
+pub struct Strobe<const SECURITY_BITS: usize, const NUM_ROUNDS: usize = { keccak::ROUNDS }> {
+    state: [u8; keccak::STATE_BYTE_LEN],
+    pos: usize,
+    pos_begin: usize,
+    is_receiver: Option<bool>,
+    prev_flags: Option<u8>,
+}
+
+/// Defines an operation, which modifies the data in place, along with its meta variant.
+macro_rules! def_op_mut {
+    ($name:ident, $meta_name:ident, $flags:expr, $doc:literal) => {
+        #[doc = $doc]
+        pub fn $name(&mut self, data: &mut [u8], more: bool) {
+            self.operate($flags, data, more);
+        }
+
+        #[doc = $doc]
+        #[doc = ""]
+        #[doc = "Meta variant, used for framing data."]
+        pub fn $meta_name(&mut self, data: &mut [u8], more: bool) {
+            self.operate($flags | FLAG_M, data, more);
+        }
+    };
+}
+
+/// Defines an operation, which only reads the data, along with its meta variant.
+macro_rules! def_op_no_mut {
+    ($name:ident, $meta_name:ident, $flags:expr, $doc:literal) => {
+        #[doc = $doc]
+        pub fn $name(&mut self, data: &[u8], more: bool) {
+            self.operate_no_mutate($flags, data, more);
+        }
+
+        #[doc = $doc]
+        #[doc = ""]
+        #[doc = "Meta variant, used for framing data."]
+        pub fn $meta_name(&mut self, data: &[u8], more: bool) {
+            self.operate_no_mutate($flags | FLAG_M, data, more);
+        }
+    };
+}
+
+impl<const SECURITY_BITS: usize, const NUM_ROUNDS: usize> Strobe<SECURITY_BITS, NUM_ROUNDS> {
+    /// STROBE rate R, in bytes, i.e. 200 - (2 * SECURITY_BITS) / 8 - 2.
+    pub const RATE: usize = keccak::STATE_BYTE_LEN - SECURITY_BITS / 4 - 2;
+
+    /// Initializes STROBE state, followed by `meta-AD` of protocol label.
+    pub fn new(protocol_label: &[u8]) -> Self {
+        const { assert!(SECURITY_BITS == 128 || SECURITY_BITS == 256) }
+
+        let mut strobe = Self {
+            state: [0u8; keccak::STATE_BYTE_LEN],
+            pos: 0,
+            pos_begin: 0,
+            is_receiver: None,
+            prev_flags: None,
+        };
+
+        strobe.state[..6].copy_from_slice(&[1, Self::RATE as u8 + 2, 1, 0, 1, 96]);
+        strobe.state[6..18].copy_from_slice(b"STROBEv1.0.2");
+        keccak::permute_nr_bytes::<NUM_ROUNDS>(&mut strobe.state);
+
+        strobe.meta_ad(protocol_label, false);
+        strobe
+    }
+
+    def_op_no_mut!(ad, meta_ad, FLAG_A, "Absorbs associated data into the state.");
+    def_op_no_mut!(key, meta_key, FLAG_A | FLAG_C, "Overwrites the state with a secret key.");
+    def_op_no_mut!(
+        send_clr,
+        meta_send_clr,
+        FLAG_A | FLAG_T,
+        "Sends a cleartext message, absorbing it into the state."
+    );
+    def_op_no_mut!(
+        recv_clr,
+        meta_recv_clr,
+        FLAG_I | FLAG_A | FLAG_T,
+        "Receives a cleartext message, absorbing it into the state."
+    );
+
+    def_op_mut!(
+        prf,
+        meta_prf,
+        FLAG_I | FLAG_A | FLAG_C,
+        "Fills the data with pseudo-random output, which doesn't depend on its initial content."
+    );
+    def_op_mut!(
+        send_enc,
+        meta_send_enc,
+        FLAG_A | FLAG_C | FLAG_T,
+        "Encrypts a plaintext message in place, for sending it."
+    );
+    def_op_mut!(
+        recv_enc,
+        meta_recv_enc,
+        FLAG_I | FLAG_A | FLAG_C | FLAG_T,
+        "Decrypts a received ciphertext message in place."
+    );
+    def_op_mut!(
+        send_mac,
+        meta_send_mac,
+        FLAG_C | FLAG_T,
+        "Fills the data with a MAC of the state, for sending it, which doesn't depend on its initial content."
+    );
+
+    /// Verifies a received MAC, in constant-time. The MAC is overwritten, while verifying it.
+    ///
+    /// # Inputs
+    ///
+    /// * `mac`: Received MAC.
+    ///
+    /// # Returns
+    ///
+    /// * Returns `Ok(())` if the MAC is valid.
+    ///   Returns Err(TurboShakeError::AuthenticationFailed) if the MAC is invalid.
+    pub fn recv_mac(&mut self, mac: &mut [u8]) -> Result<(), TurboShakeError> {
+        self.recv_mac_with_flags(FLAG_I | FLAG_C | FLAG_T, mac)
+    }
+
+    /// Verifies a received MAC, in constant-time. The MAC is overwritten, while verifying it.
+    ///
+    /// Meta variant, used for framing data.
+    pub fn meta_recv_mac(&mut self, mac: &mut [u8]) -> Result<(), TurboShakeError> {
+        self.recv_mac_with_flags(FLAG_I | FLAG_C | FLAG_T | FLAG_M, mac)
+    }
+
+    /// Zeroes `num_bytes` -bytes of the state, s.t. it can't be rolled back, for forward secrecy.
+    pub fn ratchet(&mut self, num_bytes: usize, more: bool) {
+        self.ratchet_with_flags(FLAG_C, num_bytes, more);
+    }
+
+    /// Zeroes `num_bytes` -bytes of the state, s.t. it can't be rolled back, for forward secrecy.
+    ///
+    /// Meta variant, used for framing data.
+    pub fn meta_ratchet(&mut self, num_bytes: usize, more: bool) {
+        self.ratchet_with_flags(FLAG_C | FLAG_M, num_bytes, more);
+    }
+
+    /// Returns the state, for checking it against STROBE test vectors.
+    #[cfg(test)]
+    pub(crate) fn state(&self) -> &[u8] {
+        &self.state
+    }
+
+    /// Pads current block, marking where the operation began, and permutes the state.
+    fn run_f(&mut self) {
+        self.state[self.pos] ^= self.pos_begin as u8;
+        self.state[self.pos + 1] ^= 0x04;
+        self.state[Self::RATE + 1] ^= 0x80;
+        keccak::permute_nr_bytes::<NUM_ROUNDS>(&mut self.state);
+
+        self.pos = 0;
+        self.pos_begin = 0;
+    }
+
+    /// Advances position in the rate portion, permuting the state, when it's exhausted.
+    fn advance(&mut self) {
+        self.pos += 1;
+        if self.pos == Self::RATE {
+            self.run_f();
+        }
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.state[self.pos] ^= byte;
+            self.advance();
+        }
+    }
+
+    fn absorb_and_set(&mut self, data: &mut [u8]) {
+        for byte in data.iter_mut() {
+            self.state[self.pos] ^= *byte;
+            *byte = self.state[self.pos];
+            self.advance();
+        }
+    }
+
+    fn copy_state(&mut self, data: &mut [u8]) {
+        for byte in data.iter_mut() {
+            *byte = self.state[self.pos];
+            self.advance();
+        }
+    }
+
+    fn exchange(&mut self, data: &mut [u8]) {
+        for byte in data.iter_mut() {
+            *byte ^= self.state[self.pos];
+            self.state[self.pos] ^= *byte;
+            self.advance();
+        }
+    }
+
+    fn overwrite(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.state[self.pos] = byte;
+            self.advance();
+        }
+    }
+
+    fn squeeze(&mut self, data: &mut [u8]) {
+        for byte in data.iter_mut() {
+            *byte = self.state[self.pos];
+            self.state[self.pos] = 0;
+            self.advance();
+        }
+    }
+
+    fn zero_state(&mut self, num_bytes: usize) {
+        for _ in 0..num_bytes {
+            self.state[self.pos] = 0;
+            self.advance();
+        }
+    }
+
+    /// Checks that a continued operation uses same flags as previous one, and begins a new operation, if it's not continued.
+    fn start_op(&mut self, flags: u8, more: bool) {
+        assert_eq!(flags & FLAG_K, 0, "K flag isn't supported");
+
+        if more {
+            assert_eq!(self.prev_flags, Some(flags), "continued operation must use same flags as previous one");
+            return;
+        }
+
+        self.prev_flags = Some(flags);
+        self.begin_op(flags);
+    }
+
+    /// Begins a new operation, absorbing where previous one began, along with flags of this one. For transport operations,
+    /// I flag is adjusted, s.t. it's relative to the role, fixed by first transport operation. Operations with C or K flag
+    /// set, start in a fresh block.
+    fn begin_op(&mut self, mut flags: u8) {
+        if (flags & FLAG_T) != 0 {
+            let is_op_receiving = (flags & FLAG_I) != 0;
+            let is_receiver = *self.is_receiver.get_or_insert(is_op_receiving);
+
+            flags = (flags & !FLAG_I) | ((is_receiver != is_op_receiving) as u8 * FLAG_I);
+        }
+
+        let old_begin = self.pos_begin;
+        self.pos_begin = self.pos + 1;
+
+        self.absorb(&[old_begin as u8, flags]);
+
+        if (flags & (FLAG_C | FLAG_K)) != 0 && self.pos != 0 {
+            self.run_f();
+        }
+    }
+
+    /// Runs an operation, which modifies the data in place.
+    fn operate(&mut self, flags: u8, data: &mut [u8], more: bool) {
+        self.start_op(flags, more);
+
+        match flags & !FLAG_M {
+            f if f == FLAG_C | FLAG_T => self.copy_state(data),
+            f if f == FLAG_A | FLAG_C | FLAG_T => self.absorb_and_set(data),
+            f if f == FLAG_I | FLAG_A | FLAG_C => self.squeeze(data),
+            f if (f & FLAG_C) != 0 => self.exchange(data),
+            _ => unreachable!("operation doesn't modify the data"),
+        }
+    }
+
+    /// Runs an operation, which only reads the data.
+    fn operate_no_mutate(&mut self, flags: u8, data: &[u8], more: bool) {
+        self.start_op(flags, more);
+
+        if (flags & FLAG_C) != 0 {
+            self.overwrite(data);
+        } else {
+            self.absorb(data);
+        }
+    }
+
+    fn recv_mac_with_flags(&mut self, flags: u8, mac: &mut [u8]) -> Result<(), TurboShakeError> {
+        self.operate(flags, mac, false);
+
+        let diff = mac.iter().fold(0u8, |acc, &byte| acc | byte);
+        if diff == 0 { Ok(()) } else { Err(TurboShakeError::AuthenticationFailed) }
+    }
+
+    fn ratchet_with_flags(&mut self, flags: u8, num_bytes: usize, more: bool) {
+        self.start_op(flags, more);
+        self.zero_state(num_bytes);
+    }
+}
@@ -1,6 +1,10 @@
 #![cfg(test)]
 
-use crate::{TurboShake128, TurboShake256, TurboShakeError};
+use crate::{
+    Cyclist, KetjeMajor, Kravatte, KravatteSane, KravatteSanse, LakeKeyak, MaskedTurboShake128, SpongeWrap, Strobe, Strobe128, Strobe256, Transcript, TurboKdf,
+    TurboShake128, TurboShake128Prefix, TurboShake128Reader, TurboShake256, TurboShake256Prefix, TurboShakeError, hash2field, keccak, pq_sampling, sampling,
+    turboshake128_const, turboshake256_const,
+};
 use rand::prelude::*;
 use std::cmp;
 use test_case::test_case;
@@ -425,3 +429,3056 @@ fn state_transition_should_work_in_ts256() {
         Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized)
     );
 }
+
+/// Keccak-f\[1600\] ( = Keccak-p\[1600, 24\] ) applied on all-zero state, collected from
+/// https://github.com/XKCP/XKCP/blob/master/tests/TestVectors/KeccakF-1600-IntermediateValues.txt
+#[test]
+fn test_keccak_f1600_permutation() {
+    let mut state = [0u64; keccak::LANE_CNT];
+
+    keccak::permute_nr::<24>(&mut state);
+    assert_eq!(
+        state,
+        [
+            0xf1258f7940e1dde7,
+            0x84d5ccf933c0478a,
+            0xd598261ea65aa9ee,
+            0xbd1547306f80494d,
+            0x8b284e056253d057,
+            0xff97a42d7f8e6fd4,
+            0x90fee5a0a44647c4,
+            0x8c5bda0cd6192e76,
+            0xad30a6f71b19059c,
+            0x30935ab7d08ffc64,
+            0xeb5aa93f2317d635,
+            0xa9a6e6260d712103,
+            0x81a57c16dbcf555f,
+            0x43b831cd0347c826,
+            0x01f22f1a11a5569f,
+            0x05e5635a21d9ae61,
+            0x64befef28cc970f2,
+            0x613670957bc46611,
+            0xb87c5a554fd00ecb,
+            0x8c3ee88a1ccf32c8,
+            0x940c7922ae3a2614,
+            0x1841f924a2c509e4,
+            0x16f53526e70465c2,
+            0x75f644e97f30a13b,
+            0xeaf1ff7b5ceca249,
+        ]
+    );
+
+    keccak::permute_nr::<24>(&mut state);
+    assert_eq!(
+        state,
+        [
+            0x2d5c954df96ecb3c,
+            0x6a332cd07057b56d,
+            0x093d8d1270d76b6c,
+            0x8a20d9b25569d094,
+            0x4f9c4f99e5e7f156,
+            0xf957b9a2da65fb38,
+            0x85773dae1275af0d,
+            0xfaf4f247c3d810f7,
+            0x1f1b9ee6f79a8759,
+            0xe4fecc0fee98b425,
+            0x68ce61b6b9ce68a1,
+            0xdeea66c4ba8f974f,
+            0x33c43d836eafb1f5,
+            0xe00654042719dbd9,
+            0x7cf8a9f009831265,
+            0xfd5449a6bf174743,
+            0x97ddad33d8994b40,
+            0x48ead5fc5d0be774,
+            0xe3b8c8ee55b7b03c,
+            0x91a0226e649e42e9,
+            0x900e3129e7badd7b,
+            0x202a9ec5faa3cce8,
+            0x5b3402464e1c3db6,
+            0x609f4e62a44c1059,
+            0x20d06cd26a8fbf5c,
+        ]
+    );
+}
+
+/// Test if applying inverse of Keccak-p\[1600, NUM_ROUNDS\] permutation undoes it, for random states.
+fn test_permute_inverse_nr<const NUM_ROUNDS: usize>() {
+    const ITERATION_COUNT: usize = 100;
+    let mut rng = rand::rng();
+
+    for _ in 0..ITERATION_COUNT {
+        let mut state = [0u64; keccak::LANE_CNT];
+        rng.fill(&mut state);
+
+        let mut permuted = state;
+        keccak::permute_nr::<NUM_ROUNDS>(&mut permuted);
+        assert_ne!(permuted, state);
+
+        let mut inverted = permuted;
+        keccak::permute_inverse_nr::<NUM_ROUNDS>(&mut inverted);
+        assert_eq!(inverted, state);
+    }
+}
+
+#[test]
+fn keccak_permute_inverse_should_undo_permute() {
+    test_permute_inverse_nr::<1>();
+    test_permute_inverse_nr::<4>();
+    test_permute_inverse_nr::<6>();
+    test_permute_inverse_nr::<8>();
+    test_permute_inverse_nr::<12>();
+    test_permute_inverse_nr::<16>();
+    test_permute_inverse_nr::<20>();
+    test_permute_inverse_nr::<23>();
+    test_permute_inverse_nr::<24>();
+}
+
+/// Test if Keccak-p\[1600, NUM_ROUNDS\] permutation, for round counts which are not a multiple of 4 i.e. ones used by
+/// Ketje and Kravatte, applied on state holding bytes 0, 1, ..., 199, matches independently transcribed section 3.3 of
+/// FIPS 202, which applies last `NUM_ROUNDS` rounds of Keccak-f\[1600\].
+fn test_permute_nr_known_answer<const NUM_ROUNDS: usize>(expected: [u64; keccak::LANE_CNT]) {
+    let mut state: [u64; keccak::LANE_CNT] = core::array::from_fn(|i| u64::from_le_bytes(core::array::from_fn(|j| (8 * i + j) as u8)));
+    keccak::permute_nr::<NUM_ROUNDS>(&mut state);
+
+    assert_eq!(state, expected);
+}
+
+#[test]
+fn keccak_permute_nr_should_match_known_answer() {
+    test_permute_nr_known_answer::<1>([
+        0xfe0c1ac876c45248,
+        0x4a2b0beb8baaca6a,
+        0xc030e3d38272a090,
+        0xd7145494dc1d5f9d,
+        0x65c762c067c560c2,
+        0x4a8acaaa8a4a4a2a,
+        0x398978c8b848f909,
+        0x74e4d44434a49505,
+        0x9e7e5f7f1f7f5efe,
+        0xa9c9a90928c82888,
+        0xaca5ada4bcb5bdb4,
+        0x5fd052d355d250d9,
+        0x011d2925392d1115,
+        0xc9c3cdc7d1dbc5cf,
+        0xfaf3cbc0c8c1f9f2,
+        0x9bfbababdb9bebcb,
+        0xc2fb948d665f3029,
+        0x504a4478706e6058,
+        0xfec5d0e3f2c9dcef,
+        0x2c424046640a484e,
+        0x9f19981a991b9a1c,
+        0x111f1f11151f1b11,
+        0x14171e1120233a35,
+        0x595d4155494d5145,
+        0xdbd5cdc6fff3ebe2,
+    ]);
+    test_permute_nr_known_answer::<6>([
+        0x686d6bf19198339e,
+        0xed2345aaee3c2aa6,
+        0x15f49bd6064c9c4d,
+        0xbbf8d710d8b40fd1,
+        0x63e63616c99c874f,
+        0xd9bf70a8c23bbc85,
+        0x0859bb22bb075933,
+        0x9694e050c927c5a2,
+        0x5175e71f00e49e22,
+        0xcbdcf9e6cb182d68,
+        0x50479105b892f2f0,
+        0xc216a327f86f2be4,
+        0xb2f59266c54f408e,
+        0xe66622ed1af18f5e,
+        0x9d48cb9e5865f24e,
+        0x9d52ee08859436d3,
+        0xf65ce1748b9edac5,
+        0x8a4baaceb3510eb8,
+        0xe3ec87425f23193c,
+        0x4927f43a97884b77,
+        0xde6ff0e1001cdcc0,
+        0x2cf067d82480b15d,
+        0x2188f54833596afc,
+        0x96031bf1ece23bc3,
+        0x0273693437a3f9aa,
+    ]);
+    test_permute_nr_known_answer::<23>([
+        0x068403091aef528e,
+        0x6be1d7b90e0dd0fb,
+        0x71a04cf1db1473f9,
+        0x09f3b395da3a81f3,
+        0x54269220fb021e5b,
+        0xe6a1789d03b3d22b,
+        0x4e14fa3ac8e64bee,
+        0x18566fe20280a494,
+        0xe2d216c6d0de4ac1,
+        0x308a8c1ed428b93b,
+        0xa60e4c9c899b363f,
+        0x277095c6f352313c,
+        0x3eaf462ddfc1e9ef,
+        0x003a52c1336a9ca3,
+        0x1a0e62a50c328df8,
+        0x8fdf39ef6acb3d1b,
+        0x46fa0feb5188426f,
+        0x9a9c9fd26883ea11,
+        0x0d2a189d2909df61,
+        0x322a872c7a5f961c,
+        0xb469768ddf2156b9,
+        0xb65abddca1cfde79,
+        0x37bf979d715b3ce5,
+        0xef6945f21c561694,
+        0x0f95f6886cbb7bf7,
+    ]);
+}
+
+/// Test if traced Keccak-f\[1600\] permutation, applied on all-zero state, produces intermediate values, matching
+/// https://github.com/XKCP/XKCP/blob/master/tests/TestVectors/KeccakF-1600-IntermediateValues.txt, after each step
+/// mapping of first two rounds, while final state matches output of Keccak-f\[1600\].
+#[test]
+fn test_keccak_f1600_permutation_traced() {
+    let mut state = [0u64; keccak::LANE_CNT];
+    let mut trace = Vec::new();
+
+    keccak::permute_traced::<24, _>(&mut state, |ridx, step, state| trace.push((ridx, step, *state)));
+
+    let expected_steps = [
+        keccak::StepMapping::Theta,
+        keccak::StepMapping::Rho,
+        keccak::StepMapping::Pi,
+        keccak::StepMapping::Chi,
+        keccak::StepMapping::Iota,
+    ];
+
+    assert_eq!(trace.len(), 24 * expected_steps.len());
+    trace.iter().enumerate().for_each(|(idx, (ridx, step, _))| {
+        assert_eq!(*ridx, idx / expected_steps.len());
+        assert_eq!(*step, expected_steps[idx % expected_steps.len()]);
+    });
+
+    // Listed as lanes, these are reproduced using an independent transcription of section 3.2 of FIPS 202.
+    let expected_round_states: [[u64; keccak::LANE_CNT]; 10] = [
+        // Round 0, after θ
+        [
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ],
+        // Round 0, after ρ
+        [
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ],
+        // Round 0, after π
+        [
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ],
+        // Round 0, after χ
+        [
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ],
+        // Round 0, after ι
+        [
+            0x0000000000000001,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ],
+        // Round 1, after θ
+        [
+            0x0000000000000001,
+            0x0000000000000001,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000002,
+            0x0000000000000000,
+            0x0000000000000001,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000002,
+            0x0000000000000000,
+            0x0000000000000001,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000002,
+            0x0000000000000000,
+            0x0000000000000001,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000002,
+            0x0000000000000000,
+            0x0000000000000001,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000002,
+        ],
+        // Round 1, after ρ
+        [
+            0x0000000000000001,
+            0x0000000000000002,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000010000000,
+            0x0000000000000000,
+            0x0000100000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000200000,
+            0x0000000000000000,
+            0x0000000000000400,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000010000000000,
+            0x0000000000000000,
+            0x0000200000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000200,
+            0x0000000000000000,
+            0x0000000000000004,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000008000,
+        ],
+        // Round 1, after π
+        [
+            0x0000000000000001,
+            0x0000100000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000008000,
+            0x0000000000000000,
+            0x0000000000200000,
+            0x0000000000000000,
+            0x0000200000000000,
+            0x0000000000000000,
+            0x0000000000000002,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000200,
+            0x0000000000000000,
+            0x0000000010000000,
+            0x0000000000000000,
+            0x0000000000000400,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000010000000000,
+            0x0000000000000000,
+            0x0000000000000004,
+        ],
+        // Round 1, after χ
+        [
+            0x0000000000000001,
+            0x0000100000000000,
+            0x0000000000008000,
+            0x0000000000000001,
+            0x0000100000008000,
+            0x0000000000000000,
+            0x0000200000200000,
+            0x0000000000000000,
+            0x0000200000000000,
+            0x0000000000200000,
+            0x0000000000000002,
+            0x0000000000000200,
+            0x0000000000000000,
+            0x0000000000000202,
+            0x0000000000000000,
+            0x0000000010000400,
+            0x0000000000000000,
+            0x0000000000000400,
+            0x0000000010000000,
+            0x0000000000000000,
+            0x0000010000000000,
+            0x0000000000000000,
+            0x0000010000000004,
+            0x0000000000000000,
+            0x0000000000000004,
+        ],
+        // Round 1, after ι
+        [
+            0x0000000000008083,
+            0x0000100000000000,
+            0x0000000000008000,
+            0x0000000000000001,
+            0x0000100000008000,
+            0x0000000000000000,
+            0x0000200000200000,
+            0x0000000000000000,
+            0x0000200000000000,
+            0x0000000000200000,
+            0x0000000000000002,
+            0x0000000000000200,
+            0x0000000000000000,
+            0x0000000000000202,
+            0x0000000000000000,
+            0x0000000010000400,
+            0x0000000000000000,
+            0x0000000000000400,
+            0x0000000010000000,
+            0x0000000000000000,
+            0x0000010000000000,
+            0x0000000000000000,
+            0x0000010000000004,
+            0x0000000000000000,
+            0x0000000000000004,
+        ],
+    ];
+
+    trace[..10]
+        .iter()
+        .zip(expected_round_states.iter())
+        .for_each(|((_, _, state), expected)| assert_eq!(state, expected));
+
+    let expected: [u64; keccak::LANE_CNT] = [
+        0xf1258f7940e1dde7,
+        0x84d5ccf933c0478a,
+        0xd598261ea65aa9ee,
+        0xbd1547306f80494d,
+        0x8b284e056253d057,
+        0xff97a42d7f8e6fd4,
+        0x90fee5a0a44647c4,
+        0x8c5bda0cd6192e76,
+        0xad30a6f71b19059c,
+        0x30935ab7d08ffc64,
+        0xeb5aa93f2317d635,
+        0xa9a6e6260d712103,
+        0x81a57c16dbcf555f,
+        0x43b831cd0347c826,
+        0x01f22f1a11a5569f,
+        0x05e5635a21d9ae61,
+        0x64befef28cc970f2,
+        0x613670957bc46611,
+        0xb87c5a554fd00ecb,
+        0x8c3ee88a1ccf32c8,
+        0x940c7922ae3a2614,
+        0x1841f924a2c509e4,
+        0x16f53526e70465c2,
+        0x75f644e97f30a13b,
+        0xeaf1ff7b5ceca249,
+    ];
+
+    assert_eq!(trace.last().unwrap().2, expected);
+    assert_eq!(state, expected);
+}
+
+#[test]
+fn keccak_permute_traced_should_match_permute() {
+    const ITERATION_COUNT: usize = 100;
+    let mut rng = rand::rng();
+
+    for _ in 0..ITERATION_COUNT {
+        let mut state = [0u64; keccak::LANE_CNT];
+        rng.fill(&mut state);
+
+        let mut expected = state;
+        keccak::permute(&mut expected);
+
+        let mut num_steps = 0;
+        keccak::permute_traced::<12, _>(&mut state, |ridx, _, _| {
+            assert!((12..24).contains(&ridx));
+            num_steps += 1;
+        });
+
+        assert_eq!(num_steps, 12 * 5);
+        assert_eq!(state, expected);
+    }
+}
+
+/// Given expected states after applying Keccak-f\[b\] once and twice on all-zero state, checks whether
+/// Keccak-p\[b, 12 + 2l\] permutation, over lanes of type `T`, produces them.
+fn test_keccak_f<T: keccak::Lane + PartialEq + std::fmt::Debug, const NUM_ROUNDS: usize>(state_first: [T; 25], state_second: [T; 25]) {
+    let mut state = [T::ZERO; keccak::LANE_CNT];
+
+    keccak::permute_p::<T, NUM_ROUNDS>(&mut state);
+    assert_eq!(state, state_first);
+
+    keccak::permute_p::<T, NUM_ROUNDS>(&mut state);
+    assert_eq!(state, state_second);
+}
+
+/// Test vectors are collected from https://github.com/XKCP/XKCP/blob/master/tests/TestVectors/KeccakF-200-IntermediateValues.txt
+#[test]
+fn test_keccak_f200_permutation() {
+    test_keccak_f::<u8, 18>(
+        [
+            0x3c, 0x28, 0x26, 0x84, 0x1c, 0xb3, 0x5c, 0x17, 0x1e, 0xaa, 0xe9, 0xb8, 0x11, 0x13, 0x4c, 0xea, 0xa3, 0x85, 0x2c, 0x69, 0xd2, 0xc5, 0xab, 0xaf,
+            0xea,
+        ],
+        [
+            0x1b, 0xef, 0x68, 0x94, 0x92, 0xa8, 0xa5, 0x43, 0xa5, 0x99, 0x9f, 0xdb, 0x83, 0x4e, 0x31, 0x66, 0xa1, 0x4b, 0xe8, 0x27, 0xd9, 0x50, 0x40, 0x47,
+            0x9e,
+        ],
+    );
+}
+
+/// Test vectors are collected from https://github.com/XKCP/XKCP/blob/master/tests/TestVectors/KeccakF-400-IntermediateValues.txt
+#[test]
+fn test_keccak_f400_permutation() {
+    test_keccak_f::<u16, 20>(
+        [
+            0x09f5, 0x40ac, 0x0fa9, 0x14f5, 0xe89f, 0xeca0, 0x5bd1, 0x7870, 0xeff0, 0xbf8f, 0x0337, 0x6052, 0xdc75, 0x0ec9, 0xe776, 0x5246, 0x59a1, 0x5d81,
+            0x6d95, 0x6e14, 0x633e, 0x58ee, 0x71ff, 0x714c, 0xb38e,
+        ],
+        [
+            0xe537, 0xd5d6, 0xdbe7, 0xaaf3, 0x9bc7, 0xca7d, 0x86b2, 0xfdec, 0x692c, 0x4e5b, 0x67b1, 0x15ad, 0xa7f7, 0xa66f, 0x67ff, 0x3f8a, 0x2f99, 0xe2c2,
+            0x656b, 0x5f31, 0x5ba6, 0xca29, 0xc224, 0xb85c, 0x097c,
+        ],
+    );
+}
+
+/// Test vectors are collected from https://github.com/XKCP/XKCP/blob/master/tests/TestVectors/KeccakF-800-IntermediateValues.txt
+#[test]
+fn test_keccak_f800_permutation() {
+    test_keccak_f::<u32, 22>(
+        [
+            0xe531d45d, 0xf404c6fb, 0x23a0bf99, 0xf1f8452f, 0x51ffd042, 0xe539f578, 0xf00b80a7, 0xaf973664, 0xbf5af34c, 0x227a2424, 0x88172715, 0x9f685884,
+            0xb15cd054, 0x1bf4fc0e, 0x6166fa91, 0x1a9e599a, 0xa3970a1f, 0xab659687, 0xafab8d68, 0xe74b1015, 0x34001a98, 0x4119eff3, 0x930a0e76, 0x87b28070,
+            0x11efe996,
+        ],
+        [
+            0x75bf2d0d, 0x9b610e89, 0xc826af40, 0x64cd84ab, 0xf905bdd6, 0xbc832835, 0x5f8001b9, 0x15662cce, 0x8e38c95e, 0x701fe543, 0x1b544380, 0x89acdeff,
+            0x51edb5de, 0x0e9702d9, 0x6c19aa16, 0xa2913eee, 0x60754e9a, 0x9819063c, 0xf4709254, 0xd09f9084, 0x772da259, 0x1db35df7, 0x5aa60162, 0x358825d5,
+            0xb3783bab,
+        ],
+    );
+}
+
+#[test]
+fn keccak_permute_p_over_u64_lanes_should_match_permute() {
+    const ITERATION_COUNT: usize = 100;
+    let mut rng = rand::rng();
+
+    for _ in 0..ITERATION_COUNT {
+        let mut state = [0u64; keccak::LANE_CNT];
+        rng.fill(&mut state);
+
+        let mut expected = state;
+        keccak::permute(&mut expected);
+        keccak::permute_p::<u64, 12>(&mut state);
+
+        assert_eq!(state, expected);
+    }
+
+    let mut state = [0u64; keccak::LANE_CNT];
+    let mut expected = state;
+
+    keccak::permute_nr::<24>(&mut expected);
+    keccak::permute_p::<u64, 24>(&mut state);
+
+    assert_eq!(state, expected);
+}
+
+#[test]
+fn compile_time_turboshake_should_match_runtime_turboshake() {
+    const MSG: &[u8] = b"turboshake::tests::compile_time_turboshake";
+    const MD128: [u8; 32] = turboshake128_const(MSG);
+    const MD256: [u8; 64] = turboshake256_const(MSG);
+
+    let mut md = [0u8; 32];
+    let mut hasher = TurboShake128::default();
+    assert_eq!(hasher.absorb(MSG), Ok(()));
+    assert_eq!(hasher.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+    assert_eq!(hasher.squeeze(&mut md), Ok(()));
+    assert_eq!(md, MD128);
+
+    let mut md = [0u8; 64];
+    let mut hasher = TurboShake256::default();
+    assert_eq!(hasher.absorb(MSG), Ok(()));
+    assert_eq!(hasher.finalize::<{ TurboShake256::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+    assert_eq!(hasher.squeeze(&mut md), Ok(()));
+    assert_eq!(md, MD256);
+}
+
+#[test_case(0; "message length = 0B")]
+#[test_case(135; "message length = 135B")]
+#[test_case(136; "message length = 136B")]
+#[test_case(168; "message length = 168B")]
+#[test_case(1000; "message length = 1000B")]
+fn oneshot_const_turboshake_should_match_incremental_turboshake(mlen: usize) {
+    const DLEN: usize = 512;
+    let mut rng = rand::rng();
+
+    let mut msg = vec![0u8; mlen];
+    rng.fill_bytes(&mut msg);
+
+    let mut md = [0u8; DLEN];
+    let mut hasher = TurboShake128::default();
+    assert_eq!(hasher.absorb(&msg), Ok(()));
+    assert_eq!(hasher.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+    assert_eq!(hasher.squeeze(&mut md), Ok(()));
+    assert_eq!(md, turboshake128_const::<DLEN>(&msg));
+
+    let mut md = [0u8; DLEN];
+    let mut hasher = TurboShake256::default();
+    assert_eq!(hasher.absorb(&msg), Ok(()));
+    assert_eq!(hasher.finalize::<{ TurboShake256::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+    assert_eq!(hasher.squeeze(&mut md), Ok(()));
+    assert_eq!(md, turboshake256_const::<DLEN>(&msg));
+}
+
+/// Test if loop-based Keccak-p\[1600, NUM_ROUNDS\] permutation, used with `small` feature, is equivalent to the unrolled one.
+fn test_permute_rolled_unrolled_equivalence<const NUM_ROUNDS: usize>() {
+    const ITERATION_COUNT: usize = 100;
+    let mut rng = rand::rng();
+
+    for _ in 0..ITERATION_COUNT {
+        let mut state_rolled = [0u64; keccak::LANE_CNT];
+        rng.fill(&mut state_rolled);
+
+        let mut state_unrolled = state_rolled;
+
+        keccak::permute_nr_rolled::<NUM_ROUNDS>(&mut state_rolled);
+        keccak::permute_nr_unrolled::<NUM_ROUNDS>(&mut state_unrolled);
+
+        assert_eq!(state_rolled, state_unrolled);
+    }
+}
+
+#[test]
+fn keccak_rolled_permutation_should_match_unrolled_permutation() {
+    test_permute_rolled_unrolled_equivalence::<1>();
+    test_permute_rolled_unrolled_equivalence::<4>();
+    test_permute_rolled_unrolled_equivalence::<6>();
+    test_permute_rolled_unrolled_equivalence::<8>();
+    test_permute_rolled_unrolled_equivalence::<12>();
+    test_permute_rolled_unrolled_equivalence::<15>();
+    test_permute_rolled_unrolled_equivalence::<16>();
+    test_permute_rolled_unrolled_equivalence::<20>();
+    test_permute_rolled_unrolled_equivalence::<24>();
+}
+
+#[test]
+fn bit_interleaving_should_be_invertible() {
+    const ITERATION_COUNT: usize = 1000;
+    let mut rng = rand::rng();
+
+    for _ in 0..ITERATION_COUNT {
+        let word = rng.random::<u64>();
+        let interleaved = crate::bit_interleaved::interleave(word);
+
+        for j in 0..32 {
+            assert_eq!((interleaved >> j) & 1, (word >> (2 * j)) & 1);
+            assert_eq!((interleaved >> (32 + j)) & 1, (word >> (2 * j + 1)) & 1);
+        }
+
+        assert_eq!(crate::bit_interleaved::deinterleave(interleaved), word);
+    }
+}
+
+#[test]
+fn bit_interleaved_permutation_should_match_permute() {
+    const ITERATION_COUNT: usize = 100;
+    let mut rng = rand::rng();
+
+    for _ in 0..ITERATION_COUNT {
+        let mut state = [0u64; keccak::LANE_CNT];
+        rng.fill(&mut state);
+
+        let mut expected = state;
+        keccak::permute(&mut expected);
+
+        state.iter_mut().for_each(|lane| *lane = crate::bit_interleaved::interleave(*lane));
+        crate::bit_interleaved::permute(&mut state);
+        state.iter_mut().for_each(|lane| *lane = crate::bit_interleaved::deinterleave(*lane));
+
+        assert_eq!(state, expected);
+    }
+}
+
+/// Test if lane-complemented Keccak-p\[1600, NUM_ROUNDS\] permutation is equivalent to the unrolled one.
+fn test_permute_lane_complemented<const NUM_ROUNDS: usize>() {
+    const ITERATION_COUNT: usize = 100;
+    let mut rng = rand::rng();
+
+    for _ in 0..ITERATION_COUNT {
+        let mut state_lane_complemented = [0u64; keccak::LANE_CNT];
+        rng.fill(&mut state_lane_complemented);
+
+        let mut state_expected = state_lane_complemented;
+
+        keccak::permute_nr_lane_complemented::<NUM_ROUNDS>(&mut state_lane_complemented);
+        keccak::permute_nr_unrolled::<NUM_ROUNDS>(&mut state_expected);
+
+        assert_eq!(state_lane_complemented, state_expected);
+    }
+}
+
+#[test]
+fn keccak_lane_complemented_permutation_should_match_unrolled_permutation() {
+    test_permute_lane_complemented::<1>();
+    test_permute_lane_complemented::<4>();
+    test_permute_lane_complemented::<12>();
+    test_permute_lane_complemented::<24>();
+}
+
+#[test]
+fn keccak_two_way_permutation_should_match_permute() {
+    const ITERATION_COUNT: usize = 100;
+    let mut rng = rand::rng();
+
+    for _ in 0..ITERATION_COUNT {
+        let mut state = [[0u64; 2]; keccak::LANE_CNT];
+        state.iter_mut().for_each(|lanes| rng.fill(lanes));
+
+        let mut expected = [[0u64; keccak::LANE_CNT]; 2];
+        for (i, lanes) in state.iter().enumerate() {
+            expected[0][i] = lanes[0];
+            expected[1][i] = lanes[1];
+        }
+
+        keccak::permute(&mut expected[0]);
+        keccak::permute(&mut expected[1]);
+        keccak::permute_x2(&mut state);
+
+        for (i, lanes) in state.iter().enumerate() {
+            assert_eq!(*lanes, [expected[0][i], expected[1][i]]);
+        }
+    }
+}
+
+#[test_case(0, 0, 32; "message lengths = (0B, 0B), digest length = 32B")]
+#[test_case(8192, 8192, 32; "message lengths = (8kB, 8kB), digest length = 32B")]
+#[test_case(1000, 1000, 500; "message lengths = (1000B, 1000B), digest length = 500B")]
+#[test_case(168, 1000, 64; "message lengths = (168B, 1000B), digest length = 64B")]
+#[test_case(2048, 135, 200; "message lengths = (2kB, 135B), digest length = 200B")]
+fn two_way_oneshot_turboshake_should_match_incremental_turboshake(mlen0: usize, mlen1: usize, dlen: usize) {
+    const D: u8 = 0x0b;
+    let mut rng = rand::rng();
+
+    let mut msg0 = vec![0u8; mlen0];
+    let mut msg1 = vec![0u8; mlen1];
+    rng.fill_bytes(&mut msg0);
+    rng.fill_bytes(&mut msg1);
+
+    let mut md0 = vec![0u8; dlen];
+    let mut md1 = vec![0u8; dlen + 1];
+    let mut expected_md = vec![0u8; dlen];
+
+    TurboShake128::oneshot_x2::<D>([&msg0, &msg1], [&mut md0, &mut md1]);
+
+    assert_eq!(turboshake128::<D>(&msg0).squeeze(&mut expected_md), Ok(()));
+    assert_eq!(md0, expected_md);
+
+    expected_md.resize(dlen + 1, 0);
+    assert_eq!(turboshake128::<D>(&msg1).squeeze(&mut expected_md), Ok(()));
+    assert_eq!(md1, expected_md);
+
+    let mut md0 = vec![0u8; dlen];
+    let mut md1 = vec![0u8; dlen + 1];
+    let mut expected_md = vec![0u8; dlen];
+
+    TurboShake256::oneshot_x2::<D>([&msg0, &msg1], [&mut md0, &mut md1]);
+
+    assert_eq!(turboshake256::<D>(&msg0).squeeze(&mut expected_md), Ok(()));
+    assert_eq!(md0, expected_md);
+
+    expected_md.resize(dlen + 1, 0);
+    assert_eq!(turboshake256::<D>(&msg1).squeeze(&mut expected_md), Ok(()));
+    assert_eq!(md1, expected_md);
+}
+
+/// Two-way one-shot TurboSHAKE{128, 256} must produce test vectors of https://www.ietf.org/archive/id/draft-irtf-cfrg-kangarootwelve-09.html#name-test-vectors,
+/// s.t. the WebAssembly SIMD128 backend of `keccak::permute_x2` is checked against them, when testing on that target.
+#[test]
+fn test_two_way_oneshot_turboshake() {
+    let (msg0, msg1) = (ptn(17usize.pow(2)), ptn(17usize.pow(3)));
+
+    let (mut md0, mut md1) = ([0u8; 32], [0u8; 32]);
+    TurboShake128::oneshot_x2::<0x01>([&msg0, &msg1], [&mut md0, &mut md1]);
+    assert_eq!(const_hex::encode(md0), "6232caa37353b5adb0e16e5beb97928110c5b837531339a2c9eb08014faa8ef6");
+    assert_eq!(const_hex::encode(md1), "668105870786e2aa80718487563aa06824eabc1d3a8e8b642f6d9996244fe8cf");
+
+    let (mut md0, mut md1) = ([0u8; 32], [0u8; 32]);
+    TurboShake256::oneshot_x2::<0x01>([&msg0, &msg1], [&mut md0, &mut md1]);
+    assert_eq!(const_hex::encode(md0), "a48c938770f916b09d764e29e2279b90d5fa3dd0e006ee8d6c2eb0db8893525e");
+    assert_eq!(const_hex::encode(md1), "75e8668d3a46baa7c75c3ac7d33fc2c218df38cdf0f8d70352a495bd9d5d6dfa");
+}
+
+fn test_permute_many<const N: usize>() {
+    const ITERATION_COUNT: usize = 100;
+    let mut rng = rand::rng();
+
+    for _ in 0..ITERATION_COUNT {
+        let mut state = [[0u64; N]; keccak::LANE_CNT];
+        state.iter_mut().for_each(|lanes| rng.fill(lanes));
+
+        let mut expected = [[0u64; keccak::LANE_CNT]; N];
+        for (i, lanes) in state.iter().enumerate() {
+            for j in 0..N {
+                expected[j][i] = lanes[j];
+            }
+        }
+
+        expected.iter_mut().for_each(keccak::permute);
+        keccak::permute_many::<N>(&mut state);
+
+        for (i, lanes) in state.iter().enumerate() {
+            for j in 0..N {
+                assert_eq!(lanes[j], expected[j][i]);
+            }
+        }
+    }
+}
+
+#[test]
+fn keccak_many_way_permutation_should_match_permute() {
+    test_permute_many::<2>();
+    test_permute_many::<4>();
+    test_permute_many::<8>();
+}
+
+fn test_permute_truncated<const NUM_OUT_ROWS: usize>() {
+    const ITERATION_COUNT: usize = 100;
+    let mut rng = rand::rng();
+
+    for _ in 0..ITERATION_COUNT {
+        let mut state = [0u64; keccak::LANE_CNT];
+        rng.fill(&mut state);
+
+        let mut expected = state;
+        keccak::permute(&mut expected);
+        keccak::permute_truncated::<NUM_OUT_ROWS>(&mut state);
+
+        assert_eq!(state[..5 * NUM_OUT_ROWS], expected[..5 * NUM_OUT_ROWS]);
+    }
+}
+
+#[test]
+fn keccak_truncated_permutation_should_match_permute() {
+    test_permute_truncated::<1>();
+    test_permute_truncated::<2>();
+    test_permute_truncated::<3>();
+    test_permute_truncated::<4>();
+    test_permute_truncated::<5>();
+}
+
+#[test_case(0, 32; "message length = 0B, digest length = 32B")]
+#[test_case(32, 0; "message length = 32B, digest length = 0B")]
+#[test_case(32, 31; "message length = 32B, digest length = 31B")]
+#[test_case(32, 33; "message length = 32B, digest length = 33B")]
+#[test_case(32, 40; "message length = 32B, digest length = 40B")]
+#[test_case(32, 41; "message length = 32B, digest length = 41B")]
+#[test_case(32, 64; "message length = 32B, digest length = 64B")]
+#[test_case(135, 65; "message length = 135B, digest length = 65B")]
+#[test_case(168, 500; "message length = 168B, digest length = 500B")]
+fn oneshot_turboshake_should_match_incremental_turboshake(mlen: usize, dlen: usize) {
+    const D: u8 = 0x1f;
+    let mut rng = rand::rng();
+
+    let mut msg = vec![0u8; mlen];
+    rng.fill_bytes(&mut msg);
+
+    let mut md = vec![0u8; dlen];
+    let mut expected_md = vec![0u8; dlen];
+
+    TurboShake128::oneshot::<D>(&msg, &mut md);
+    assert_eq!(turboshake128::<D>(&msg).squeeze(&mut expected_md), Ok(()));
+    assert_eq!(md, expected_md);
+
+    TurboShake256::oneshot::<D>(&msg, &mut md);
+    assert_eq!(turboshake256::<D>(&msg).squeeze(&mut expected_md), Ok(()));
+    assert_eq!(md, expected_md);
+}
+
+#[test_case(0, 32; "prefix length = 0B, message length = 32B")]
+#[test_case(48, 32; "prefix length = 48B, message length = 32B")]
+#[test_case(135, 1; "prefix length = 135B, message length = 1B")]
+#[test_case(136, 32; "prefix length = 136B, message length = 32B")]
+#[test_case(167, 2; "prefix length = 167B, message length = 2B")]
+#[test_case(168, 32; "prefix length = 168B, message length = 32B")]
+#[test_case(400, 300; "prefix length = 400B, message length = 300B")]
+fn hashing_with_prefix_should_match_hashing_prefixed_message(plen: usize, mlen: usize) {
+    const DLEN: usize = 64;
+    const ITERATION_COUNT: usize = 4;
+    let mut rng = rand::rng();
+
+    let mut prefix = vec![0u8; plen];
+    rng.fill_bytes(&mut prefix);
+
+    let prefix128 = TurboShake128Prefix::new(&prefix);
+    let prefix256 = TurboShake256Prefix::new(&prefix);
+
+    let mut hasher128 = TurboShake128::from(prefix128);
+    let mut hasher256 = TurboShake256::from(prefix256);
+
+    for _ in 0..ITERATION_COUNT {
+        let mut msg = vec![0u8; mlen];
+        rng.fill_bytes(&mut msg);
+
+        let prefixed_msg = [prefix.as_slice(), msg.as_slice()].concat();
+
+        let mut md = [0u8; DLEN];
+        let mut expected_md = [0u8; DLEN];
+
+        prefix128.reset_to_prefix(&mut hasher128);
+        assert_eq!(hasher128.absorb(&msg), Ok(()));
+        assert_eq!(hasher128.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+        assert_eq!(hasher128.squeeze(&mut md), Ok(()));
+
+        assert_eq!(
+            turboshake128::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(&prefixed_msg).squeeze(&mut expected_md),
+            Ok(())
+        );
+        assert_eq!(md, expected_md);
+
+        prefix256.reset_to_prefix(&mut hasher256);
+        assert_eq!(hasher256.absorb(&msg), Ok(()));
+        assert_eq!(hasher256.finalize::<{ TurboShake256::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+        assert_eq!(hasher256.squeeze(&mut md), Ok(()));
+
+        assert_eq!(
+            turboshake256::<{ TurboShake256::DEFAULT_DOMAIN_SEPARATOR }>(&prefixed_msg).squeeze(&mut expected_md),
+            Ok(())
+        );
+        assert_eq!(md, expected_md);
+
+        let mut hasher = TurboShake128::with_prefix(&prefix);
+        assert_eq!(hasher.absorb(&msg), Ok(()));
+        assert_eq!(hasher.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+        assert_eq!(hasher.squeeze(&mut md), Ok(()));
+
+        assert_eq!(
+            turboshake128::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(&prefixed_msg).squeeze(&mut expected_md),
+            Ok(())
+        );
+        assert_eq!(md, expected_md);
+    }
+}
+
+#[test]
+fn reset_to_prefix_should_reset_finalized_instance() {
+    let mut md = [0u8; 32];
+    let mut expected_md = [0u8; 32];
+
+    let mut hasher = TurboShake128::default();
+    assert_eq!(hasher.absorb(b"message 0"), Ok(()));
+    assert_eq!(hasher.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+
+    TurboShake128Prefix::new(b"").reset_to_prefix(&mut hasher);
+    assert_eq!(hasher.squeeze(&mut md), Err(TurboShakeError::StillInDataAbsorptionPhase));
+    assert_eq!(hasher.absorb(b"message 1"), Ok(()));
+    assert_eq!(hasher.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+    assert_eq!(hasher.squeeze(&mut md), Ok(()));
+
+    assert_eq!(
+        turboshake128::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(b"message 1").squeeze(&mut expected_md),
+        Ok(())
+    );
+    assert_eq!(md, expected_md);
+}
+
+#[test_case(0; "message length = 0B")]
+#[test_case(32; "message length = 32B")]
+#[test_case(168; "message length = 168B")]
+#[test_case(1000; "message length = 1000B")]
+fn forked_readers_should_match_finalized_hashers(mlen: usize) {
+    const DLEN: usize = 500;
+    let mut rng = rand::rng();
+
+    let mut msg = vec![0u8; mlen];
+    rng.fill_bytes(&mut msg);
+
+    let mut md = [0u8; DLEN];
+    let mut expected_md = [0u8; DLEN];
+
+    let mut hasher128 = TurboShake128::default();
+    assert_eq!(hasher128.absorb(&msg), Ok(()));
+
+    let mut reader = hasher128.fork_finalize::<0x0b>().expect("must fork");
+    reader.squeeze(&mut md[..DLEN / 2]);
+    reader.squeeze(&mut md[DLEN / 2..]);
+    assert_eq!(turboshake128::<0x0b>(&msg).squeeze(&mut expected_md), Ok(()));
+    assert_eq!(md, expected_md);
+
+    hasher128.fork_finalize::<0x0c>().expect("must fork").squeeze(&mut md);
+    assert_eq!(turboshake128::<0x0c>(&msg).squeeze(&mut expected_md), Ok(()));
+    assert_eq!(md, expected_md);
+
+    let mut hasher256 = TurboShake256::default();
+    assert_eq!(hasher256.absorb(&msg), Ok(()));
+
+    let mut reader = hasher256.fork_finalize::<0x0b>().expect("must fork");
+    reader.squeeze(&mut md[..DLEN / 2]);
+    reader.squeeze(&mut md[DLEN / 2..]);
+    assert_eq!(turboshake256::<0x0b>(&msg).squeeze(&mut expected_md), Ok(()));
+    assert_eq!(md, expected_md);
+
+    hasher256.fork_finalize::<0x0c>().expect("must fork").squeeze(&mut md);
+    assert_eq!(turboshake256::<0x0c>(&msg).squeeze(&mut expected_md), Ok(()));
+    assert_eq!(md, expected_md);
+}
+
+#[test]
+fn forking_should_leave_hasher_absorbing() {
+    let mut md = [0u8; 32];
+    let mut expected_md = [0u8; 32];
+
+    let mut hasher = TurboShake128::default();
+    assert_eq!(hasher.absorb(b"message"), Ok(()));
+    assert!(hasher.fork_finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>().is_ok());
+
+    assert_eq!(hasher.absorb(b" continued"), Ok(()));
+    assert_eq!(hasher.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+    assert_eq!(hasher.squeeze(&mut md), Ok(()));
+    assert_eq!(
+        turboshake128::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(b"message continued").squeeze(&mut expected_md),
+        Ok(())
+    );
+    assert_eq!(md, expected_md);
+
+    assert_eq!(
+        hasher.fork_finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>().err(),
+        Some(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized)
+    );
+}
+
+#[test_case(0; "skip 0B")]
+#[test_case(1; "skip 1B")]
+#[test_case(135; "skip 135B")]
+#[test_case(136; "skip 136B")]
+#[test_case(167; "skip 167B")]
+#[test_case(168; "skip 168B")]
+#[test_case(169; "skip 169B")]
+#[test_case(4096; "skip 4kB")]
+fn skipping_should_match_squeezing_and_discarding(num_bytes: usize) {
+    const DLEN: usize = 400;
+    const LEADING_BYTES: usize = 7;
+
+    let mut discarded = vec![0u8; LEADING_BYTES + num_bytes];
+    let mut md = [0u8; DLEN];
+    let mut expected_md = [0u8; DLEN];
+
+    let mut hasher128 = TurboShake128::default();
+    assert_eq!(hasher128.absorb(b"message"), Ok(()));
+
+    let mut reader = hasher128.fork_finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>().expect("must fork");
+    let mut expected_reader = reader.clone();
+
+    reader.squeeze(&mut discarded[..LEADING_BYTES]);
+    reader.skip(num_bytes as u64);
+    assert_eq!(reader.position(), (LEADING_BYTES + num_bytes) as u64);
+    reader.squeeze(&mut md);
+    assert_eq!(reader.position(), (LEADING_BYTES + num_bytes + DLEN) as u64);
+
+    expected_reader.squeeze(&mut discarded);
+    expected_reader.squeeze(&mut expected_md);
+    assert_eq!(md, expected_md);
+
+    let mut hasher256 = TurboShake256::default();
+    assert_eq!(hasher256.absorb(b"message"), Ok(()));
+
+    let mut reader = hasher256.fork_finalize::<{ TurboShake256::DEFAULT_DOMAIN_SEPARATOR }>().expect("must fork");
+    let mut expected_reader = reader.clone();
+
+    reader.squeeze(&mut discarded[..LEADING_BYTES]);
+    reader.skip(num_bytes as u64);
+    assert_eq!(reader.position(), (LEADING_BYTES + num_bytes) as u64);
+    reader.squeeze(&mut md);
+    assert_eq!(reader.position(), (LEADING_BYTES + num_bytes + DLEN) as u64);
+
+    expected_reader.squeeze(&mut discarded);
+    expected_reader.squeeze(&mut expected_md);
+    assert_eq!(md, expected_md);
+}
+
+#[test]
+fn restoring_checkpoint_should_rewind_reader() {
+    let mut hasher = TurboShake128::default();
+    assert_eq!(hasher.absorb(b"message"), Ok(()));
+
+    let mut reader = hasher.fork_finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>().expect("must fork");
+    reader.skip(100);
+
+    let checkpoint = reader.checkpoint();
+
+    let mut md = [0u8; 300];
+    reader.squeeze(&mut md);
+    assert_eq!(reader.position(), 400);
+
+    reader.restore(&checkpoint);
+    assert_eq!(reader.position(), 100);
+
+    let mut restored_md = [0u8; 300];
+    reader.squeeze(&mut restored_md);
+    assert_eq!(md, restored_md);
+
+    let mut forked_reader = TurboShake128Reader::from(checkpoint);
+    assert_eq!(forked_reader.position(), 100);
+
+    let mut forked_md = [0u8; 300];
+    forked_reader.squeeze(&mut forked_md);
+    assert_eq!(md, forked_md);
+}
+
+#[test]
+fn finalized_hasher_should_skip_and_restore_like_reader() {
+    let mut hasher128 = TurboShake128::default();
+    assert_eq!(hasher128.absorb(b"message"), Ok(()));
+    assert_eq!(hasher128.skip(1), Err(TurboShakeError::StillInDataAbsorptionPhase));
+    assert_eq!(hasher128.checkpoint().err(), Some(TurboShakeError::StillInDataAbsorptionPhase));
+
+    let mut reader = hasher128.fork_finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>().expect("must fork");
+    assert_eq!(hasher128.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+    assert_eq!(hasher128.position(), 0);
+
+    let mut md = [0u8; 300];
+    let mut expected_md = [0u8; 300];
+
+    assert_eq!(hasher128.skip(169), Ok(()));
+    reader.skip(169);
+    assert_eq!(hasher128.position(), reader.position());
+
+    let checkpoint = hasher128.checkpoint().expect("must take checkpoint");
+    assert_eq!(hasher128.squeeze(&mut md), Ok(()));
+    reader.squeeze(&mut expected_md);
+    assert_eq!(md, expected_md);
+    assert_eq!(hasher128.position(), 469);
+
+    let mut restored = TurboShake128::default();
+    restored.restore(&checkpoint);
+    assert_eq!(restored.position(), 169);
+    assert_eq!(restored.squeeze(&mut md), Ok(()));
+    assert_eq!(md, expected_md);
+
+    let mut forked_reader = TurboShake128Reader::from(checkpoint);
+    forked_reader.squeeze(&mut md);
+    assert_eq!(md, expected_md);
+
+    let mut hasher256 = TurboShake256::default();
+    assert_eq!(hasher256.absorb(b"message"), Ok(()));
+
+    let mut reader = hasher256.fork_finalize::<{ TurboShake256::DEFAULT_DOMAIN_SEPARATOR }>().expect("must fork");
+    assert_eq!(hasher256.finalize::<{ TurboShake256::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+
+    assert_eq!(hasher256.skip(137), Ok(()));
+    reader.skip(137);
+
+    let checkpoint = hasher256.checkpoint().expect("must take checkpoint");
+    assert_eq!(hasher256.squeeze(&mut md), Ok(()));
+    reader.squeeze(&mut expected_md);
+    assert_eq!(md, expected_md);
+    assert_eq!(hasher256.position(), reader.position());
+
+    hasher256.restore(&checkpoint);
+    assert_eq!(hasher256.squeeze(&mut md), Ok(()));
+    assert_eq!(md, expected_md);
+}
+
+fn sampling_reader() -> TurboShake128Reader {
+    let mut hasher = TurboShake128::default();
+    assert_eq!(hasher.absorb(b"turboshake::tests::sampling"), Ok(()));
+
+    hasher.fork_finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>().expect("must fork")
+}
+
+/// Sampled values are pinned, so that any change in the way output bytes are consumed, which would break reproducibility
+/// of sampled values, gets caught. These are regression vectors, generated using this implementation.
+#[test]
+fn sampled_values_should_be_reproducible() {
+    let mut reader = sampling_reader();
+
+    let ints: Vec<u64> = (0..8).map(|_| sampling::u64_below(&mut reader, 1000)).collect();
+    assert_eq!(ints, [875, 1, 750, 361, 66, 639, 625, 687]);
+    assert_eq!(sampling::u64_below(&mut reader, (1u64 << 63) + 1), 7050716732984515788);
+
+    assert_eq!(sampling::f64_unit(&mut reader), 0.8775686301543666);
+    assert_eq!(sampling::f64_unit(&mut reader), 0.10208269270692993);
+
+    let mut items: Vec<usize> = (0..10).collect();
+    sampling::shuffle(&mut reader, &mut items);
+    assert_eq!(items, [5, 0, 8, 9, 6, 2, 1, 4, 3, 7]);
+
+    let mut indices = [0usize; 5];
+    sampling::distinct_indices(&mut reader, 1 << 20, &mut indices);
+    assert_eq!(indices, [43486, 724623, 585815, 401956, 893638]);
+
+    assert_eq!(reader.position(), 67);
+}
+
+#[test]
+fn finalized_hasher_should_sample_same_as_reader() {
+    let mut reader = sampling_reader();
+
+    let mut hasher = TurboShake128::default();
+    assert_eq!(hasher.absorb(b"turboshake::tests::sampling"), Ok(()));
+    assert_eq!(hasher.finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>(), Ok(()));
+
+    assert_eq!(sampling::u64_below(&mut hasher, 1000), sampling::u64_below(&mut reader, 1000));
+    assert_eq!(sampling::f64_unit(&mut hasher), sampling::f64_unit(&mut reader));
+
+    let mut items: Vec<usize> = (0..10).collect();
+    let mut expected_items = items.clone();
+    sampling::shuffle(&mut hasher, &mut items);
+    sampling::shuffle(&mut reader, &mut expected_items);
+    assert_eq!(items, expected_items);
+
+    assert_eq!(hasher.position(), reader.position());
+}
+
+#[test]
+#[should_panic(expected = "TurboSHAKE256 instance must be finalized, before sampling from it")]
+fn sampling_from_unfinalized_hasher_should_panic() {
+    let mut hasher = TurboShake256::default();
+    sampling::u64(&mut hasher);
+}
+
+#[test_case(1; "n = 1")]
+#[test_case(2; "n = 2")]
+#[test_case(3; "n = 3")]
+#[test_case(256; "n = 256")]
+#[test_case(257; "n = 257")]
+#[test_case(1000; "n = 1000")]
+#[test_case(u64::MAX; "n = 2^64 - 1")]
+fn sampled_integers_should_be_below_bound(n: u64) {
+    const ITERATION_COUNT: usize = 1000;
+    let mut reader = sampling_reader();
+
+    let num_bytes_per_attempt = (u64::BITS - (n - 1).leading_zeros()).div_ceil(u8::BITS) as u64;
+
+    for _ in 0..ITERATION_COUNT {
+        let position = reader.position();
+        assert!(sampling::u64_below(&mut reader, n) < n);
+        assert_eq!((reader.position() - position) % num_bytes_per_attempt.max(1), 0);
+    }
+
+    for _ in 0..ITERATION_COUNT {
+        let value = sampling::f64_unit(&mut reader);
+        assert!((0.0..1.0).contains(&value));
+    }
+}
+
+#[test]
+fn sampled_integers_should_cover_small_range() {
+    const N: usize = 6;
+    const ITERATION_COUNT: usize = 6000;
+    let mut reader = sampling_reader();
+
+    let mut counts = [0usize; N];
+    for _ in 0..ITERATION_COUNT {
+        counts[sampling::usize_below(&mut reader, N)] += 1;
+    }
+
+    // Each value is expected to show up ~1000 times, allowing for a wide margin
+    assert!(counts.iter().all(|&count| (800..1200).contains(&count)));
+}
+
+#[test]
+fn shuffle_and_distinct_indices_should_be_well_formed() {
+    const ITERATION_COUNT: usize = 100;
+    let mut reader = sampling_reader();
+
+    for _ in 0..ITERATION_COUNT {
+        let mut items: Vec<usize> = (0..50).collect();
+        sampling::shuffle(&mut reader, &mut items);
+
+        let mut sorted_items = items.clone();
+        sorted_items.sort_unstable();
+        assert!(sorted_items.into_iter().eq(0..50));
+
+        let mut indices = [0usize; 8];
+        sampling::distinct_indices(&mut reader, 8, &mut indices);
+
+        indices.sort_unstable();
+        assert!(indices.into_iter().eq(0..8));
+    }
+}
+
+/// SHAKE{128, 256} i.e. Keccak\[c\] sponge, built on Keccak-f\[1600\], processing one byte at a time, so that sampling routines
+/// can be checked with SHAKE substituted for TurboSHAKE, as specified in FIPS 203 and FIPS 204.
+struct Shake<const RATE: usize> {
+    state: [u64; keccak::LANE_CNT],
+    offset: usize,
+}
+
+impl<const RATE: usize> Shake<RATE> {
+    fn new(msg: &[u8]) -> Self {
+        let mut shake = Self {
+            state: [0u64; keccak::LANE_CNT],
+            offset: 0,
+        };
+
+        for &byte in msg {
+            shake.xor_byte(byte);
+            shake.offset += 1;
+
+            if shake.offset == RATE {
+                keccak::permute_nr::<24>(&mut shake.state);
+                shake.offset = 0;
+            }
+        }
+
+        shake.xor_byte(0x1f);
+        shake.offset = RATE - 1;
+        shake.xor_byte(0x80);
+
+        keccak::permute_nr::<24>(&mut shake.state);
+        shake.offset = 0;
+
+        shake
+    }
+
+    fn xor_byte(&mut self, byte: u8) {
+        self.state[self.offset / 8] ^= (byte as u64) << (8 * (self.offset % 8));
+    }
+}
+
+impl<const RATE: usize> sampling::XofReader for Shake<RATE> {
+    fn squeeze(&mut self, out: &mut [u8]) {
+        for byte in out.iter_mut() {
+            if self.offset == RATE {
+                keccak::permute_nr::<24>(&mut self.state);
+                self.offset = 0;
+            }
+
+            *byte = (self.state[self.offset / 8] >> (8 * (self.offset % 8))) as u8;
+            self.offset += 1;
+        }
+    }
+}
+
+type Shake128 = Shake<168>;
+type Shake256 = Shake<136>;
+
+#[test]
+fn test_shake() {
+    use sampling::XofReader;
+
+    let mut md = [0u8; 32];
+    Shake128::new(b"").squeeze(&mut md);
+    assert_eq!(const_hex::encode(md), "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26");
+
+    let mut md = [0u8; 64];
+    Shake256::new(b"").squeeze(&mut md);
+    assert_eq!(
+        const_hex::encode(md),
+        "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762fd75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be"
+    );
+}
+
+/// Seeds ρ || j || i, as used for sampling matrix A in ML-KEM and ML-DSA, for j, i ∈ [0, 2).
+fn pq_sampling_seeds() -> Vec<[u8; 34]> {
+    let rho: [u8; 32] = core::array::from_fn(|i| i as u8);
+
+    (0..4)
+        .map(|k| {
+            let mut seed = [0u8; 34];
+            seed[..32].copy_from_slice(&rho);
+            seed[32] = k % 2;
+            seed[33] = k / 2;
+            seed
+        })
+        .collect()
+}
+
+#[test]
+fn uniform_polys_should_match_shake_based_sampling() {
+    use sampling::XofReader;
+
+    for seed in pq_sampling_seeds() {
+        // SampleNTT of FIPS 203, squeezing 3 -bytes at a time
+        let mut xof = Shake128::new(&seed);
+        let mut expected = Vec::with_capacity(pq_sampling::N);
+        while expected.len() < pq_sampling::N {
+            let mut c = [0u8; 3];
+            xof.squeeze(&mut c);
+
+            let d1 = c[0] as u32 + 256 * (c[1] as u32 % 16);
+            let d2 = c[1] as u32 / 16 + 16 * c[2] as u32;
+
+            if d1 < pq_sampling::MLKEM_Q {
+                expected.push(d1);
+            }
+            if d2 < pq_sampling::MLKEM_Q && expected.len() < pq_sampling::N {
+                expected.push(d2);
+            }
+        }
+
+        assert_eq!(pq_sampling::uniform_poly_mod_3329(&mut Shake128::new(&seed)).to_vec(), expected);
+
+        // RejNTTPoly of FIPS 204, squeezing 3 -bytes at a time
+        let mut xof = Shake128::new(&seed);
+        let mut expected = Vec::with_capacity(pq_sampling::N);
+        while expected.len() < pq_sampling::N {
+            let mut c = [0u8; 3];
+            xof.squeeze(&mut c);
+
+            let z = 65536 * (c[2] as u32 % 128) + 256 * c[1] as u32 + c[0] as u32;
+            if z < pq_sampling::MLDSA_Q {
+                expected.push(z);
+            }
+        }
+
+        assert_eq!(pq_sampling::uniform_poly_mod_8380417(&mut Shake128::new(&seed)).to_vec(), expected);
+    }
+}
+
+fn test_cbd_poly<const ETA: usize>() {
+    use sampling::XofReader;
+
+    for seed in pq_sampling_seeds() {
+        let mut bytes = vec![0u8; 64 * ETA];
+        Shake256::new(&seed[..33]).squeeze(&mut bytes);
+
+        // SamplePolyCBD of FIPS 203, on bits of PRF output
+        let bits: Vec<i32> = bytes.iter().flat_map(|&byte| (0..8).map(move |j| ((byte >> j) & 1) as i32)).collect();
+        let expected: Vec<i32> = bits
+            .chunks_exact(2 * ETA)
+            .map(|group| group[..ETA].iter().sum::<i32>() - group[ETA..].iter().sum::<i32>())
+            .collect();
+
+        assert_eq!(pq_sampling::cbd_poly::<ETA, _>(&mut Shake256::new(&seed[..33])).to_vec(), expected);
+    }
+
+    let mut reader = sampling_reader();
+    for _ in 0..100 {
+        let poly = pq_sampling::cbd_poly::<ETA, _>(&mut reader);
+        assert!(poly.iter().all(|&coeff| coeff.unsigned_abs() as usize <= ETA));
+    }
+}
+
+#[test]
+fn cbd_polys_should_match_shake_based_sampling() {
+    test_cbd_poly::<2>();
+    test_cbd_poly::<3>();
+}
+
+/// Checks `bounded_poly` against polynomials sampled with ρ' = (0, 1, ..., 63) and r ∈ {0, 1}, using an independent
+/// transcription of `RejBoundedPoly` of FIPS 204, on Python's hashlib SHAKE256, where i -th digit is η - coefficient i.
+fn test_bounded_poly<const ETA: u8>(expected: [&str; 2]) {
+    for (r, expected) in expected.into_iter().enumerate() {
+        let mut seed = [0u8; 66];
+        seed[..64].iter_mut().enumerate().for_each(|(i, byte)| *byte = i as u8);
+        seed[64..].copy_from_slice(&(r as u16).to_le_bytes());
+
+        let poly = pq_sampling::bounded_poly::<ETA, _>(&mut Shake256::new(&seed));
+        let encoded: String = poly.iter().map(|&coeff| format!("{:x}", ETA as i32 - coeff)).collect();
+        assert_eq!(encoded, expected);
+    }
+
+    let mut reader = sampling_reader();
+    for _ in 0..100 {
+        let poly = pq_sampling::bounded_poly::<ETA, _>(&mut reader);
+        assert!(poly.iter().all(|&coeff| coeff.unsigned_abs() <= ETA as u32));
+    }
+}
+
+#[test]
+fn bounded_polys_should_match_shake_based_sampling() {
+    test_bounded_poly::<2>([
+        "1001404201210302403410020133000433112100321002431201203121012220334201130440424444000022234133042434000440134234443032011203110113014002223343104031422211123331142042443421104120313001103313223113320424002122120114014230322022030040420413133243202031140004",
+        "3030204203403120144414000120031322240430143133110042414043102204400420331000301044021013303022410104440301214423040020144234041332314231140322001401131224104012444023110010104431101413302113040022410242444012043230400241403313442240422204034334322213442133",
+    ]);
+    test_bounded_poly::<4>([
+        "1015251507453605785058116508215524317051067283201632444555777885735055687480756125366680405222346407271233165484262310083682236132546221201078532535042131823208600086356232668128560223484662230215415230364853357550011617225022683253813727801186386678817534",
+        "8535755808244600517086336311002414086225002083504768353521406443464781382381275631265564452665516541832113455776224620805746083644542754033872768428802000642227057734852860560824707413570215456117855342625800261650711225020704380322828887660637486003886421",
+    ]);
+}
+
+/// Checks `sample_in_ball` against polynomial sampled with ρ = (0, 1, ..., λ/4 - 1), using an independent transcription of
+/// `SampleInBall` of FIPS 204, on Python's hashlib SHAKE256, where i -th digit is 1 - coefficient i.
+fn test_sample_in_ball<const TAU: usize>(lambda: usize, expected: &str) {
+    let seed: Vec<u8> = (0..lambda / 4).map(|i| i as u8).collect();
+
+    let poly = pq_sampling::sample_in_ball::<TAU, _>(&mut Shake256::new(&seed));
+    let encoded: String = poly.iter().map(|&coeff| format!("{}", 1 - coeff)).collect();
+    assert_eq!(encoded, expected);
+
+    let mut reader = sampling_reader();
+    for _ in 0..100 {
+        let poly = pq_sampling::sample_in_ball::<TAU, _>(&mut reader);
+
+        assert_eq!(poly.iter().filter(|&&coeff| coeff != 0).count(), TAU);
+        assert!(poly.iter().all(|&coeff| coeff.abs() <= 1));
+    }
+}
+
+#[test]
+fn sampled_ball_polys_should_match_shake_based_sampling() {
+    test_sample_in_ball::<39>(
+        128,
+        "1112111010111111111211111111112111111111111101111111111112111211110112111111122111111111110011111110111111111111101011111111111111111111011111111011111101120111111111101111111111101111111101111111011110011211110221111111111110120111111121111011221111111111",
+    );
+    test_sample_in_ball::<49>(
+        192,
+        "1101111110111011121110111111101101111111121111111121111210111111221111011111121111111111111011111112111211112111211111111111111111121111001112221111111011111112111011111101101111112111111120010112111110111111111211121011111011010102011121101111111111111111",
+    );
+    test_sample_in_ball::<60>(
+        256,
+        "1011111101111212112111111011111101110111111210111221111211011110211112011011112110111111211111111110111111211101221110011122221211110111010111111111111210112011110011121111111011111101111111111200111121011121212111111111121111111211121111110111110111111110",
+    );
+}
+
+/// Regression vectors, generated using this implementation, with SHAKE{128, 256} substituted, as no published test vectors
+/// cover individual sampling routines. Both the seed and XOF inputs follow FIPS 203 and FIPS 204.
+#[test]
+fn pq_sampling_with_shake_should_match_regression_vectors() {
+    let seed = pq_sampling_seeds()[0];
+
+    assert_eq!(
+        pq_sampling::uniform_poly_mod_3329(&mut Shake128::new(&seed))[..8],
+        [481, 1919, 1434, 2359, 327, 1066, 3001, 649]
+    );
+    assert_eq!(
+        pq_sampling::uniform_poly_mod_8380417(&mut Shake128::new(&seed))[..8],
+        [7905761, 7863978, 1275290, 4366663, 7850937, 4248201, 2710427, 4706185]
+    );
+    assert_eq!(
+        pq_sampling::cbd_poly::<2, _>(&mut Shake256::new(&seed[..33]))[..16],
+        [-1, 0, 1, 1, -2, 1, 0, 0, -1, 1, -1, -1, -2, 0, 1, -1]
+    );
+    assert_eq!(
+        pq_sampling::bounded_poly::<2, _>(&mut Shake256::new(&seed))[..16],
+        [2, 0, -2, 0, -1, 0, -1, 1, 0, -1, 2, -2, 1, 2, 1, 2]
+    );
+    assert_eq!(
+        pq_sampling::bounded_poly::<4, _>(&mut Shake256::new(&seed))[..16],
+        [-3, 0, 2, -4, 2, 1, -1, -2, -2, -3, 3, -3, -1, 2, -3, -3]
+    );
+
+    let poly = pq_sampling::sample_in_ball::<39, _>(&mut Shake256::new(&seed[..32]));
+    let non_zero_coeffs: Vec<(usize, i32)> = poly.iter().copied().enumerate().filter(|&(_, coeff)| coeff != 0).take(8).collect();
+    assert_eq!(non_zero_coeffs, [(3, -1), (7, 1), (9, 1), (19, -1), (30, -1), (44, 1), (57, -1), (61, -1)]);
+}
+
+/// Computes TurboSHAKE256 of concatenated `parts`, with domain separator `D`, without relying on `TurboKdf`.
+fn turboshake256_of_parts<const D: u8>(parts: &[&[u8]], out: &mut [u8]) {
+    let mut hasher = TurboShake256::default();
+    parts.iter().for_each(|part| hasher.absorb(part).expect("must absorb data"));
+    hasher.finalize::<D>().expect("must finalize");
+    hasher.squeeze(out).expect("must squeeze data");
+}
+
+#[test_case(b"", b"", b"", 0; "empty inputs and output")]
+#[test_case(b"salt", b"input keying material", b"encryption key", 32; "32B subkey")]
+#[test_case(&[0xa5; 200], &[0x5a; 300], &[0x3c; 150], 500; "inputs and output longer than rate")]
+fn turbokdf_should_follow_its_construction(salt: &[u8], ikm: &[u8], label: &[u8], okm_len: usize) {
+    let mut prk = [0u8; TurboKdf::PRK_BYTE_LEN];
+    turboshake256_of_parts::<{ TurboKdf::EXTRACT_DOMAIN_SEPARATOR }>(
+        &[&(salt.len() as u64).to_le_bytes(), salt, &(ikm.len() as u64).to_le_bytes(), ikm],
+        &mut prk,
+    );
+
+    let mut expected = vec![0u8; okm_len];
+    turboshake256_of_parts::<{ TurboKdf::EXPAND_DOMAIN_SEPARATOR }>(
+        &[
+            &(prk.len() as u64).to_le_bytes(),
+            &prk,
+            &(label.len() as u64).to_le_bytes(),
+            label,
+            &(okm_len as u64).to_le_bytes(),
+        ],
+        &mut expected,
+    );
+
+    let mut computed = vec![0u8; okm_len];
+    TurboKdf::extract(salt, ikm).expand(label, &mut computed);
+
+    assert_eq!(computed, expected);
+}
+
+#[test]
+fn turbokdf_inputs_should_be_injectively_encoded() {
+    let mut okm_a = [0u8; 32];
+    let mut okm_b = [0u8; 32];
+
+    TurboKdf::extract(b"ab", b"c").expand(b"label", &mut okm_a);
+    TurboKdf::extract(b"a", b"bc").expand(b"label", &mut okm_b);
+    assert_ne!(okm_a, okm_b);
+
+    let kdf = TurboKdf::extract(b"salt", b"ikm");
+    kdf.expand(b"encryption key", &mut okm_a);
+    kdf.expand(b"mac key", &mut okm_b);
+    assert_ne!(okm_a, okm_b);
+
+    let mut longer_okm = [0u8; 64];
+    kdf.expand(b"encryption key", &mut longer_okm);
+    assert_ne!(okm_a, longer_okm[..32]);
+}
+
+/// Regression vectors, generated using this implementation, as `TurboKdf` is specific to this crate.
+#[test]
+fn turbokdf_should_match_regression_vectors() {
+    let kdf = TurboKdf::extract(b"salt", b"input keying material");
+
+    let mut enc_key = [0u8; 32];
+    let mut mac_key = [0u8; 16];
+    kdf.expand(b"encryption key", &mut enc_key);
+    kdf.expand(b"mac key", &mut mac_key);
+
+    let mut unsalted_key = [0u8; 32];
+    TurboKdf::extract(b"", b"input keying material").expand(b"", &mut unsalted_key);
+
+    assert_eq!(const_hex::encode(enc_key), "c076fccd48d5b9703f18edd14c0d54a5cdf73e4cbab306b64368206796dbd949");
+    assert_eq!(const_hex::encode(mac_key), "0e1b0bb38ef1395c6ca396f13b2bab63");
+    assert_eq!(
+        const_hex::encode(unsalted_key),
+        "db348d4846cca9610873082ee7f70898de133475eabbab1c42cbdb020058fd58"
+    );
+}
+
+/// Incremental interface to the test-only SHAKE, which buffers the message until finalization.
+#[derive(Default)]
+struct ShakeHasher<const RATE: usize> {
+    msg: Vec<u8>,
+}
+
+impl<const RATE: usize> hash2field::Xof for ShakeHasher<RATE> {
+    const SECURITY_BITS: usize = (keccak::LANE_CNT * 8 - RATE) * 4;
+    type Reader = Shake<RATE>;
+
+    fn absorb(&mut self, msg: &[u8]) {
+        self.msg.extend_from_slice(msg);
+    }
+
+    fn finalize(self) -> Self::Reader {
+        Shake::new(&self.msg)
+    }
+}
+
+const RFC9380_SHAKE128_DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHAKE128";
+const RFC9380_SHAKE128_LONG_DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHAKE128-long-DST-111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111";
+const RFC9380_SHAKE256_DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHAKE256";
+
+/// Messages of expand_message_xof test vectors, from appendix K.5 and K.6 of RFC 9380.
+fn rfc9380_msg(name: &str) -> Vec<u8> {
+    match name {
+        "q128" => format!("q128_{}", "q".repeat(128)).into_bytes(),
+        "a512" => format!("a512_{}", "a".repeat(512)).into_bytes(),
+        _ => name.as_bytes().to_vec(),
+    }
+}
+
+#[test_case(RFC9380_SHAKE128_DST, "", "86518c9cd86581486e9485aa74ab35ba150d1c75c88e26b7043e44e2acd735a2"; "empty message")]
+#[test_case(RFC9380_SHAKE128_DST, "abc", "8696af52a4d862417c0763556073f47bc9b9ba43c99b505305cb1ec04a9ab468"; "abc")]
+#[test_case(RFC9380_SHAKE128_DST, "abcdef0123456789", "912c58deac4821c3509dbefa094df54b34b8f5d01a191d1d3108a2c89077acca"; "abcdef0123456789")]
+#[test_case(RFC9380_SHAKE128_DST, "q128", "1adbcc448aef2a0cebc71dac9f756b22e51839d348e031e63b33ebb50faeaf3f"; "q128")]
+#[test_case(RFC9380_SHAKE128_DST, "a512", "df3447cc5f3e9a77da10f819218ddf31342c310778e0e4ef72bbaecee786a4fe"; "a512")]
+#[test_case(RFC9380_SHAKE128_DST, "", "7314ff1a155a2fb99a0171dc71b89ab6e3b2b7d59e38e64419b8b6294d03ffee42491f11370261f436220ef787f8f76f5b26bdcd850071920ce023f3ac46847744f4612b8714db8f5db83205b2e625d95afd7d7b4d3094d3bdde815f52850bb41ead9822e08f22cf41d615a303b0d9dde73263c049a7b9898208003a739a2e57"; "empty message, 128B output")]
+#[test_case(RFC9380_SHAKE128_DST, "a512", "9d763a5ce58f65c91531b4100c7266d479a5d9777ba761693d052acd37d149e7ac91c796a10b919cd74a591a1e38719fb91b7203e2af31eac3bff7ead2c195af7d88b8bc0a8adf3d1e90ab9bed6ddc2b7f655dd86c730bdeaea884e73741097142c92f0e3fc1811b699ba593c7fbd81da288a29d423df831652e3a01a9374999"; "a512, 128B output")]
+#[test_case(RFC9380_SHAKE128_LONG_DST, "", "827c6216330a122352312bccc0c8d6e7a146c5257a776dbd9ad9d75cd880fc53"; "long DST, empty message")]
+#[test_case(RFC9380_SHAKE128_LONG_DST, "abc", "690c8d82c7213b4282c6cb41c00e31ea1d3e2005f93ad19bbf6da40f15790c5c"; "long DST, abc")]
+#[test_case(RFC9380_SHAKE128_LONG_DST, "abcdef0123456789", "979e3a15064afbbcf99f62cc09fa9c85028afcf3f825eb0711894dcfc2f57057"; "long DST, abcdef0123456789")]
+#[test_case(RFC9380_SHAKE128_LONG_DST, "q128", "c5a9220962d9edc212c063f4f65b609755a1ed96e62f9db5d1fd6adb5a8dc52b"; "long DST, q128")]
+#[test_case(RFC9380_SHAKE128_LONG_DST, "a512", "f7b96a5901af5d78ce1d071d9c383cac66a1dfadb508300ec6aeaea0d62d5d62"; "long DST, a512")]
+#[test_case(RFC9380_SHAKE128_LONG_DST, "abc", "41b7ffa7a301b5c1441495ebb9774e2a53dbbf4e54b9a1af6a20fd41eafd69ef7b9418599c5545b1ee422f363642b01d4a53449313f68da3e49dddb9cd25b97465170537d45dcbdf92391b5bdff344db4bd06311a05bca7dcd360b6caec849c299133e5c9194f4e15e3e23cfaab4003fab776f6ac0bfae9144c6e2e1c62e7d57"; "long DST, abc, 128B output")]
+fn expand_message_xof_with_shake128_should_match_rfc9380_vectors(dst: &[u8], msg: &str, expected: &str) {
+    let mut uniform_bytes = vec![0u8; expected.len() / 2];
+    hash2field::expand_message_xof::<ShakeHasher<168>>(&rfc9380_msg(msg), dst, &mut uniform_bytes).expect("must expand message");
+
+    assert_eq!(const_hex::encode(uniform_bytes), expected);
+}
+
+#[test_case("", "2ffc05c48ed32b95d72e807f6eab9f7530dd1c2f013914c8fed38c5ccc15ad76"; "empty message")]
+#[test_case("a512", "9181ead5220b1963f1b5951f35547a5ea86a820562287d6ca4723633d17ccbbc"; "a512")]
+fn expand_message_xof_with_shake256_should_match_rfc9380_vectors(msg: &str, expected: &str) {
+    let mut uniform_bytes = vec![0u8; expected.len() / 2];
+    hash2field::expand_message_xof::<ShakeHasher<136>>(&rfc9380_msg(msg), RFC9380_SHAKE256_DST, &mut uniform_bytes).expect("must expand message");
+
+    assert_eq!(const_hex::encode(uniform_bytes), expected);
+}
+
+#[test]
+fn expand_message_xof_should_reject_too_long_output() {
+    let mut uniform_bytes = vec![0u8; u16::MAX as usize + 1];
+
+    assert_eq!(
+        hash2field::expand_message_xof::<TurboShake128>(b"abc", RFC9380_SHAKE128_DST, &mut uniform_bytes),
+        Err(TurboShakeError::ExpandedMessageTooLong)
+    );
+    assert_eq!(
+        hash2field::expand_message_xof::<TurboShake128>(b"abc", RFC9380_SHAKE128_DST, &mut uniform_bytes[1..]),
+        Ok(())
+    );
+}
+
+#[test]
+fn hash_to_field_should_reject_empty_domain_separation_tag() {
+    let mut uniform_bytes = [0u8; 32];
+    let mut elems = [0u8; 32];
+
+    assert_eq!(
+        hash2field::expand_message_xof::<TurboShake128>(b"abc", b"", &mut uniform_bytes),
+        Err(TurboShakeError::EmptyDomainSeparationTag)
+    );
+    assert_eq!(
+        hash2field::hash_to_field::<TurboShake128>(b"abc", b"", &[0x7f; 16], &mut elems),
+        Err(TurboShakeError::EmptyDomainSeparationTag)
+    );
+    assert_eq!(hash2field::expand_message_xof::<TurboShake128>(b"abc", b"D", &mut uniform_bytes), Ok(()));
+}
+
+/// Reduces a 256 -bit integer, given as its high and low 128 -bit halves, modulo Mersenne prime 2^127 - 1, using the
+/// fact that 2^127 ≡ 1, instead of generic reduction of `hash_to_field`.
+fn reduce_mod_mersenne127(hi: u128, lo: u128) -> u128 {
+    const P: u128 = (1 << 127) - 1;
+
+    let fold = |x: u128| {
+        let r = (x & P) + (x >> 127);
+        if r >= P { r - P } else { r }
+    };
+
+    fold(fold(fold(hi) << 1) + fold(lo))
+}
+
+#[test_case(b"", b"QUUX-V01-CS02-with-expander-TurboSHAKE128"; "empty message")]
+#[test_case(b"abc", b"QUUX-V01-CS02-with-expander-TurboSHAKE128"; "abc")]
+#[test_case(&[0xff; 300], RFC9380_SHAKE128_LONG_DST; "long message and long DST")]
+fn hash_to_field_should_match_mersenne_reduction(msg: &[u8], dst: &[u8]) {
+    const P: u128 = (1 << 127) - 1;
+
+    // L = ⌈(127 + 128) / 8⌉ = 32 -bytes per element
+    let mut uniform_bytes = [0u8; 3 * 32];
+    hash2field::expand_message_xof::<TurboShake128>(msg, dst, &mut uniform_bytes).expect("must expand message");
+
+    let mut elems = [0u8; 3 * 16];
+    hash2field::hash_to_field::<TurboShake128>(msg, dst, &P.to_be_bytes(), &mut elems).expect("must hash to field");
+
+    for (chunk, elem) in uniform_bytes.chunks_exact(32).zip(elems.chunks_exact(16)) {
+        let hi = u128::from_be_bytes(chunk[..16].try_into().unwrap());
+        let lo = u128::from_be_bytes(chunk[16..].try_into().unwrap());
+
+        assert_eq!(u128::from_be_bytes(elem.try_into().unwrap()), reduce_mod_mersenne127(hi, lo));
+    }
+}
+
+/// Regression vectors, generated using this implementation, as RFC 9380 doesn't specify TurboSHAKE instantiations.
+#[test]
+fn hash2field_with_turboshake_should_match_regression_vectors() {
+    let mut uniform_bytes = [0u8; 32];
+
+    hash2field::expand_message_xof::<TurboShake128>(b"abc", b"QUUX-V01-CS02-with-expander-TurboSHAKE128", &mut uniform_bytes).expect("must expand message");
+    assert_eq!(
+        const_hex::encode(uniform_bytes),
+        "56cfa48b4041ae7dba1262370a14a1b7a1b7e94fa5a1c3333793b6b6d412208b"
+    );
+
+    hash2field::expand_message_xof::<TurboShake256>(b"abc", &[b'D'; 256], &mut uniform_bytes).expect("must expand message");
+    assert_eq!(
+        const_hex::encode(uniform_bytes),
+        "8027e263821aa1856576b52f303d1ad499d88f11a0f29cbf34bc90af07272360"
+    );
+
+    // Prime modulus of P-256 base field
+    let p = const_hex::decode("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff").unwrap();
+
+    let mut u = [0u8; 64];
+    hash2field::hash_to_field::<TurboShake128>(b"abc", b"QUUX-V01-CS02-with-P256_XOF:TurboSHAKE128_SSWU_RO_", &p, &mut u).expect("must hash to field");
+    assert_eq!(
+        const_hex::encode(u),
+        "78ce54dfe57ace5311a8d648027c8dc1eb57c4072b902cdb68c4e85b5869a6a4c28556164eb3047df673924a6d431ff7da51e301f6bcda264cebf08a826defb6"
+    );
+}
+
+/// Runs a fixed sequence of transcript operations, returning hex-encoded challenges and random bytes, derived along the way.
+fn transcript_outputs<const NUM_ROUNDS: usize>() -> [String; 5] {
+    let mut transcript = Transcript::<NUM_ROUNDS>::new_nr(b"test protocol");
+    transcript.append_message(b"some label", b"some data");
+
+    let mut challenge = [0u8; 32];
+    transcript.challenge_bytes(b"challenge", &mut challenge);
+    let simple = const_hex::encode(challenge);
+
+    let mut transcript = Transcript::<NUM_ROUNDS>::new_nr(b"test protocol");
+    let data = [99u8; 1024];
+    transcript.append_message(b"step1", b"some data");
+
+    for _ in 0..32 {
+        transcript.challenge_bytes(b"challenge", &mut challenge);
+        transcript.append_message(b"bigdata", &data);
+        transcript.append_message(b"challengedata", &challenge);
+    }
+    transcript.challenge_bytes(b"challenge", &mut challenge);
+    let complex = const_hex::encode(challenge);
+
+    let mut long_challenge = [0u8; 200];
+    transcript.append_u64(b"u64", 0x0123456789abcdef);
+    transcript.challenge_bytes(b"long challenge", &mut long_challenge);
+    let long = const_hex::encode(&long_challenge[168..]);
+
+    let mut rng = transcript
+        .build_rng()
+        .rekey_with_witness_bytes(b"witness", b"witness data")
+        .finalize(&[0x42; 32]);
+
+    let mut random_bytes = [0u8; 32];
+    rng.fill_bytes(&mut random_bytes);
+    let rng0 = const_hex::encode(random_bytes);
+    rng.fill_bytes(&mut random_bytes);
+    let rng1 = const_hex::encode(random_bytes);
+
+    [simple, complex, long, rng0, rng1]
+}
+
+/// With 24 rounds, transcripts are compatible with Merlin v3.0.0. Expected outputs are computed using `merlin` crate, with
+/// fresh randomness of random number generator being 32 bytes of 0x42.
+#[test]
+fn transcript_with_24_rounds_should_match_merlin() {
+    assert_eq!(
+        transcript_outputs::<24>(),
+        [
+            "d5a21972d0d5fe320c0d263fac7fffb8145aa640af6e9bca177c03c7efcf0615",
+            "19759d48a45e5f7ddaee1967e11dd3b3e64a44117bb68e2d40fa0be56fff7253",
+            "2ed9c56ef079272d269c88a30b46558ee563cb2c9788b3ee707956976d820237",
+            "9f0d65fe2659c74b35d8180576cab945fda20f2467fb1acef9e08f504b93e8dd",
+            "9f888b59c137d7a8ab81e727fcdddbd999baddedf37fcb8d2f57017923ae92aa",
+        ]
+    );
+}
+
+/// Regression vectors of transcripts on Keccak-p[1600, 12], generated using this implementation, for checking
+/// interoperability of independent implementations.
+#[test]
+fn transcript_should_match_regression_vectors() {
+    assert_eq!(
+        transcript_outputs::<12>(),
+        [
+            "22d4f9d8f06886de5caba1defd3239e41bb9ef29522d3d61382ad5ae4244b2fa",
+            "b628a8ad8e3be689594b73518bf51ff4e159b86a46354911d3ea1f55d87f65d9",
+            "00d76eb33503a00c8ece8700eae8fd2ebafa8fabdc1e7da7df4a5db7cfb88526",
+            "590cbd5575db8fa923ad2cb51ed403411b691874fb041f6869489f7ff31a8741",
+            "778cb55aec3e764b66a9a02ed2115e96632a016c38cf870056cfaf2efac09602",
+        ]
+    );
+}
+
+#[test]
+fn transcript_should_bind_labels_lengths_and_forks() {
+    let challenge = |transcript: &mut Transcript, len: usize| {
+        let mut challenge = vec![0u8; len];
+        transcript.challenge_bytes(b"challenge", &mut challenge);
+        challenge
+    };
+
+    let mut transcript_a = Transcript::new(b"test protocol");
+    let mut transcript_b = Transcript::new(b"test protocol");
+    transcript_a.append_message(b"ab", b"c");
+    transcript_b.append_message(b"a", b"bc");
+    assert_ne!(challenge(&mut transcript_a, 32), challenge(&mut transcript_b, 32));
+
+    let transcript = Transcript::new(b"test protocol");
+    assert_ne!(challenge(&mut transcript.clone(), 32), challenge(&mut transcript.clone(), 64)[..32]);
+
+    let mut fork_a = transcript.fork(b"sub-protocol a");
+    let mut fork_b = transcript.fork(b"sub-protocol b");
+    assert_ne!(challenge(&mut fork_a, 32), challenge(&mut fork_b, 32));
+    assert_ne!(challenge(&mut fork_a.clone(), 32), challenge(&mut transcript.clone(), 32));
+}
+
+/// Runs a sequence of STROBE operations, given as (name, meta, more, input, expected output), on a STROBE-128 instance over
+/// Keccak-p[1600, NUM_ROUNDS], checking output of each operation, when it's given, and returning the final state.
+fn run_strobe_ops<const NUM_ROUNDS: usize>(protocol_label: &[u8], ops: &[(&str, bool, bool, &str, &str)]) -> String {
+    let mut strobe = Strobe::<128, NUM_ROUNDS>::new(protocol_label);
+
+    for &(name, meta, more, input, expected_output) in ops {
+        let mut data = const_hex::decode(input).unwrap();
+
+        match (name, meta) {
+            ("AD", false) => strobe.ad(&data, more),
+            ("AD", true) => strobe.meta_ad(&data, more),
+            ("KEY", false) => strobe.key(&data, more),
+            ("KEY", true) => strobe.meta_key(&data, more),
+            ("PRF", false) => strobe.prf(&mut data, more),
+            ("PRF", true) => strobe.meta_prf(&mut data, more),
+            ("send_CLR", false) => strobe.send_clr(&data, more),
+            ("send_CLR", true) => strobe.meta_send_clr(&data, more),
+            ("recv_CLR", false) => strobe.recv_clr(&data, more),
+            ("recv_CLR", true) => strobe.meta_recv_clr(&data, more),
+            ("send_ENC", false) => strobe.send_enc(&mut data, more),
+            ("send_ENC", true) => strobe.meta_send_enc(&mut data, more),
+            ("recv_ENC", false) => strobe.recv_enc(&mut data, more),
+            ("recv_ENC", true) => strobe.meta_recv_enc(&mut data, more),
+            ("send_MAC", false) => strobe.send_mac(&mut data, more),
+            ("send_MAC", true) => strobe.meta_send_mac(&mut data, more),
+            // MACs of test vectors are expected to be rejected
+            ("recv_MAC", false) => assert_eq!(strobe.recv_mac(&mut data), Err(TurboShakeError::AuthenticationFailed)),
+            ("recv_MAC", true) => assert_eq!(strobe.meta_recv_mac(&mut data), Err(TurboShakeError::AuthenticationFailed)),
+            ("RATCHET", false) => strobe.ratchet(data.len(), more),
+            ("RATCHET", true) => strobe.meta_ratchet(data.len(), more),
+            _ => unreachable!("unknown STROBE operation"),
+        }
+
+        if !expected_output.is_empty() {
+            assert_eq!(const_hex::encode(&data), expected_output, "output of {name} operation");
+        }
+    }
+
+    const_hex::encode(strobe.state())
+}
+
+/// STROBE test vectors, as distributed with `strobe-rs` v0.8.1 crate, are for STROBE-128 with Keccak-f[1600] i.e. 24 rounds.
+#[test]
+fn strobe_with_24_rounds_should_match_simple_test_vector() {
+    let ops = [
+        ("KEY", false, false, "303130313031", ""),
+        ("AD", false, false, "68656c6c6f2c20686f772061726520796f7520676f6f64207369723f", ""),
+        ("PRF", false, false, "00000000000000000000000000000000", "5ce86d0815c02a27d8bdd923f2cb0bd8"),
+        ("send_ENC", false, false, "686920686f772061726520796f75", "dc0c857f045d9941fed8acbfd024"),
+        ("recv_ENC", false, false, "686920686f772061726520796f75", "aad1a176d7add6ef904f1e1acf53"),
+        ("send_MAC", false, false, "00000000000000000000000000000000", "a05d94e152949460c67d648e91bc6d53"),
+        ("recv_MAC", false, false, "686920686f772061726520796f75", ""),
+        ("send_CLR", false, false, "686920686f772061726520796f75", "686920686f772061726520796f75"),
+        ("recv_CLR", false, false, "686920686f772061726520796f75", "686920686f772061726520796f75"),
+        ("RATCHET", false, false, "0000000000000000000000000000000000000000000000000000000000000000", ""),
+    ];
+
+    assert_eq!(
+        run_strobe_ops::<24>(b"custom string", &ops),
+        "000000000000000000000000000000000000000000000000000000000000000086bee3a828fc80f502b7f390dbca230c8ba1cea5cb93a958c443277dd0ab0625745898f5ebabc9ec1800db4ea4fb7ad776a49b8aef66874397f0b8f6072df52b2eeb9871046865aea090d503e68cd583fe65064fed4c6a9c8d6f190a9f506e80a79d2b2a5ef56ede1fe2c575fae052505a4032221f796bd09cdc723245c572a6eb500be48728fd0461b09d7813cbf384d479999a79168eadfd567bb20adc9c3cb6b5e7ddf9c86d73"
+    );
+}
+
+#[test]
+fn strobe_with_24_rounds_should_match_meta_test_vector() {
+    let ops = [
+        ("KEY", false, false, "303130313031", ""),
+        ("AD", true, false, "68656c6c6f2c20686f772061726520796f7520676f6f64207369723f", ""),
+        ("PRF", false, false, "00000000000000000000000000000000", "87e57623d5c80f6d1083473a288ccdd7"),
+        ("send_ENC", true, false, "686920686f772061726520796f75", "f443f226de75b9f1e5d87c0b3bf0"),
+        ("recv_ENC", true, false, "686920686f772061726520796f75", "a14f034475e956e83d9d2f4e5c1e"),
+        ("send_MAC", true, false, "00000000000000000000000000000000", "ea4dcfc763fb0492c94c2d21c3cd8083"),
+        ("recv_MAC", true, false, "686920686f772061726520796f75", ""),
+        ("send_CLR", true, false, "686920686f772061726520796f75", "686920686f772061726520796f75"),
+        ("recv_CLR", true, false, "686920686f772061726520796f75", "686920686f772061726520796f75"),
+        ("RATCHET", true, false, "0000000000000000000000000000000000000000000000000000000000000000", ""),
+    ];
+
+    assert_eq!(
+        run_strobe_ops::<24>(b"custom string number 2, that's a pretty long string", &ops),
+        "00000000000000000000000000000000000000000000000000000000000000009f8ed329237711ff2a4225b0b01d0ef66dcc86a64ae97d28ad9d04507e638c649eae8b798fd36beca30ba37c5ebaf3049dc34107a33c847307a00b60201f3de9ad27fb745acd57d16c334f07cdb34c9518f89ce835912da28cda7f0b67b0e3a4ab5dff54984bcb50136ee74e22569e4a75201f019b5e4e156a1bd9289659683958eadc5b7f2846d110fb6c0faaba64cce760b000c11317e80c4f169fd7e9280ceb4b5e4e97ed50b4"
+    );
+}
+
+#[test]
+fn strobe_with_24_rounds_should_match_streaming_test_vector() {
+    let ops = [
+        ("KEY", false, false, "30313031303130313030313030313031303130313031303130313031303031303031", ""),
+        ("KEY", false, true, "30313031303130313030313030313031303130313031303130313031303031303031", ""),
+        ("AD", false, false, "68656c6c6f2c20686f772061726520796f7520676f6f64207369723f203f3f3f3f", ""),
+        ("AD", false, true, "68656c6c6f2c20686f772061726520796f7520676f6f64207369723f203f3f3f3f", ""),
+        ("AD", false, false, "68656c6c6f2c20686f772061726520796f7520676f6f64207369723f203f3f3f3f", ""),
+    ];
+
+    assert_eq!(
+        run_strobe_ops::<24>(b"custom string number 2, that's a pretty long string", &ops),
+        "5117b46c2d842655c1be2a69f64f16aaaad2c0050fe2ac5446afe44345a9b10d044c8b3ec8005a9e362c0a431ab5c4d8228c2f890ae56ad3fef4404aa6cc76704b503d627553ae9635d329cdfa86ed29ec0dd79787ff3fcefdee7463c053ef3b4a4fa7c8eb89a6372df2c4ccfc7469d7447bd19a67940642334706e5ff6b1ef58514e55c6b5c6921c58eb7cb5c57978c92c42e598926fcfdcd9705fb948ed6fe9027c65fb0659c98a9c9668d523dfa2b27bde76224944503b686901c989fedac34994dd16daedf00"
+    );
+}
+
+/// Boundary test vector runs operations KEY, AD and send_ENC in turn, where i-th operation takes i -bytes input 0, 1, .., i - 1,
+/// s.t. each possible position of an operation boundary in the rate portion is exercised.
+#[test]
+fn strobe_with_24_rounds_should_match_boundary_test_vector() {
+    let inputs: Vec<String> = (1..=168usize)
+        .map(|i| const_hex::encode((0..i).map(|b| b as u8).collect::<Vec<u8>>()))
+        .collect();
+    let ops: Vec<(&str, bool, bool, &str, &str)> = inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| (["KEY", "AD", "send_ENC"][i % 3], false, false, input.as_str(), ""))
+        .collect();
+
+    let mut strobe_ops = ops.clone();
+    strobe_ops.last_mut().unwrap().4 = "948f9286c1af3ad870bfb7066ada250275410f4ac39d7a4dc99717b79f410dd58d6466752f3db43a0be6c45010f2b62b6b22819358e98740949cde517bba07b2be3e3c7238144ee401bca1f847ceabba57888b87c7efa232ba27b093fb7f1a94ca35b7fccfcea7973958f5e49d27e73150499b7d5c55a52e4c58a3d1066a7d14aebf5824b9f9fe654f1eb1a872efc69ecd8bbb36a476c1c407ff85f5da713be7f2b7936b0a8c75a2";
+
+    assert_eq!(
+        run_strobe_ops::<24>(b"custom string number 2, that's a pretty long string", &strobe_ops),
+        "75a286e3980b3179e7d81609c092a572fd37e986cd67bf761e898d057533fd46deab8392bd446b04d6d5ba501edf0f03b8d2b9eb27c667e62bfb987eeb86b6bd45c614d806d9094692d9cc7fd54c38a8181bf376e1ec14bf09c9271091d3f06d4a37baeb1799929d02cdba6ed9ab80c08c8cd8801921413e77f226a295de611b8047c3854a9b11e27fc8a6a2b961f87cc661f6a064006c4009d851e6ca5daae517092319dd0a1c1daf69a247eb40f24d121e51ac6dc7d8337c33e345fd2cf2a28cde5ade52324b57"
+    );
+}
+
+/// Regression vector of STROBE-128 with Keccak-p[1600, 12], generated using this implementation, running same operations as
+/// simple test vector.
+#[test]
+fn strobe_should_match_regression_vector() {
+    let ops = [
+        ("KEY", false, false, "303130313031", ""),
+        ("AD", false, false, "68656c6c6f2c20686f772061726520796f7520676f6f64207369723f", ""),
+        ("PRF", false, false, "00000000000000000000000000000000", ""),
+        ("send_ENC", false, false, "686920686f772061726520796f75", ""),
+        ("recv_ENC", false, false, "686920686f772061726520796f75", ""),
+        ("send_MAC", false, false, "00000000000000000000000000000000", ""),
+        ("recv_MAC", false, false, "686920686f772061726520796f75", ""),
+        ("send_CLR", false, false, "686920686f772061726520796f75", ""),
+        ("recv_CLR", false, false, "686920686f772061726520796f75", ""),
+        ("RATCHET", false, false, "0000000000000000000000000000000000000000000000000000000000000000", ""),
+    ];
+
+    assert_eq!(
+        run_strobe_ops::<12>(b"custom string", &ops),
+        "0000000000000000000000000000000000000000000000000000000000000000752b534b424a82eab539d2a4618bbd2271c1bca479941f47903b25b84b2e1eebc83a74c160b79f769e31babc45f067c738cd2589d3fac3adc974c9ac0f5945599f50a5a9cae4d04e6bc213f0a0b0358c9d197a1ec8779b6c8ecb94d1600b412b5b8c5160b716d5bdbc1bad5c6f89a4fdfbcc5a269ff4935b6637a878997daab44bbecfe81a62f245c5fe1ebd68e7099632803076bad28f8174bfc2dbe9a74e53c8d584670ea424fa"
+    );
+}
+
+#[test]
+fn strobe_should_roundtrip_messages_and_reject_forged_macs() {
+    let mut alice = Strobe128::new(b"turboshake::tests::strobe");
+    let mut bob = Strobe128::new(b"turboshake::tests::strobe");
+
+    alice.key(b"shared secret key", false);
+    bob.key(b"shared secret key", false);
+
+    for (i, msg_len) in [0usize, 1, 165, 166, 167, 500].into_iter().enumerate() {
+        let msg: Vec<u8> = (0..msg_len).map(|b| (b * 7 + i) as u8).collect();
+
+        let mut ct = msg.clone();
+        alice.meta_send_clr(&(msg_len as u32).to_le_bytes(), false);
+        alice.send_enc(&mut ct[..msg_len / 2], false);
+        alice.send_enc(&mut ct[msg_len / 2..], true);
+
+        let mut pt = ct.clone();
+        bob.meta_recv_clr(&(msg_len as u32).to_le_bytes(), false);
+        bob.recv_enc(&mut pt, false);
+        assert_eq!(pt, msg);
+
+        let mut mac = [0u8; 16];
+        alice.send_mac(&mut mac, false);
+
+        if i % 2 == 0 {
+            assert_eq!(bob.recv_mac(&mut mac.clone()), Ok(()));
+        } else {
+            let mut forged_mac = mac;
+            forged_mac[i % 16] ^= 1;
+            assert_eq!(bob.clone().recv_mac(&mut forged_mac), Err(TurboShakeError::AuthenticationFailed));
+            assert_eq!(bob.recv_mac(&mut mac), Ok(()));
+        }
+
+        alice.ratchet(32, false);
+        bob.ratchet(32, false);
+    }
+
+    let mut alice_prf = [0u8; 32];
+    let mut bob_prf = [0u8; 32];
+    alice.prf(&mut alice_prf, false);
+    bob.prf(&mut bob_prf, false);
+    assert_eq!(alice_prf, bob_prf);
+
+    let mut strobe256 = Strobe256::new(b"turboshake::tests::strobe");
+    let mut strobe256_prf = [0u8; 32];
+    strobe256.key(b"shared secret key", false);
+    strobe256.prf(&mut strobe256_prf, false);
+    assert_ne!(strobe256_prf, [0u8; 32]);
+}
+
+#[test]
+fn cyclist_hash_should_follow_its_construction() {
+    let msg = b"turboshake::tests::cyclist";
+
+    // Down(msg, 0x03) on all-zero state, as a fresh object starts in up phase, followed by Up(32, 0x40), in hash mode.
+    let mut state = [0u8; keccak::STATE_BYTE_LEN];
+    state.iter_mut().zip(msg).for_each(|(s, &m)| *s ^= m);
+    state[msg.len()] ^= 0x01;
+    state[keccak::STATE_BYTE_LEN - 1] ^= 0x01;
+    keccak::permute_nr_bytes::<{ keccak::ROUNDS }>(&mut state);
+
+    let mut digest = [0u8; 32];
+    let mut hasher = Cyclist::hash();
+    hasher.absorb(msg);
+    hasher.squeeze(&mut digest);
+
+    assert_eq!(digest, state[..32]);
+}
+
+#[test]
+fn cyclist_hash_should_separate_absorb_calls() {
+    let mut one_call = [0u8; 32];
+    let mut two_calls = [0u8; 32];
+
+    let mut hasher = Cyclist::hash();
+    hasher.absorb(b"turboshake");
+    hasher.squeeze(&mut one_call);
+
+    let mut hasher = Cyclist::hash();
+    hasher.absorb(b"turbo");
+    hasher.absorb(b"shake");
+    hasher.squeeze(&mut two_calls);
+
+    assert_ne!(one_call, two_calls);
+
+    // Squeezing more than hash rate starts with same bytes, as squeezing a single block.
+    let mut long_digest = [0u8; 400];
+    let mut hasher = Cyclist::hash();
+    hasher.absorb(&ptn(1000));
+    hasher.squeeze(&mut long_digest);
+
+    let mut short_digest = [0u8; 168];
+    let mut hasher = Cyclist::hash();
+    hasher.absorb(&ptn(1000));
+    hasher.squeeze(&mut short_digest);
+
+    assert_eq!(long_digest[..168], short_digest);
+}
+
+/// Seals `msg` with Cyclist in keyed mode, returning ciphertext and 16 -bytes tag.
+fn cyclist_seal(key: &[u8], nonce: &[u8], ad: &[u8], msg: &[u8]) -> (Vec<u8>, [u8; 16]) {
+    let mut cyclist = Cyclist::keyed(key, b"", b"");
+    cyclist.absorb(nonce);
+    cyclist.absorb(ad);
+
+    let mut ct = msg.to_vec();
+    cyclist.encrypt(&mut ct);
+
+    let mut tag = [0u8; 16];
+    cyclist.squeeze(&mut tag);
+
+    (ct, tag)
+}
+
+/// Opens `ct` with Cyclist in keyed mode, returning plaintext only if recomputed tag matches `tag`.
+fn cyclist_open(key: &[u8], nonce: &[u8], ad: &[u8], ct: &[u8], tag: &[u8; 16]) -> Option<Vec<u8>> {
+    let mut cyclist = Cyclist::keyed(key, b"", b"");
+    cyclist.absorb(nonce);
+    cyclist.absorb(ad);
+
+    let mut pt = ct.to_vec();
+    cyclist.decrypt(&mut pt);
+
+    let mut expected_tag = [0u8; 16];
+    cyclist.squeeze(&mut expected_tag);
+
+    (expected_tag == *tag).then_some(pt)
+}
+
+#[test_case(0; "empty message")]
+#[test_case(1; "single byte message")]
+#[test_case(175; "message one byte shorter than keyed squeeze rate")]
+#[test_case(176; "message of keyed squeeze rate")]
+#[test_case(177; "message one byte longer than keyed squeeze rate")]
+#[test_case(196; "message of keyed absorb rate")]
+#[test_case(1000; "long message")]
+fn cyclist_should_roundtrip_and_reject_forgeries(msg_len: usize) {
+    let key = b"turboshake::tests::cyclist::key";
+    let nonce = b"unique nonce";
+    let ad = ptn(msg_len / 3);
+    let msg = ptn(msg_len);
+
+    let (ct, tag) = cyclist_seal(key, nonce, &ad, &msg);
+    if msg_len > 0 {
+        assert_ne!(ct, msg);
+    }
+    assert_eq!(cyclist_open(key, nonce, &ad, &ct, &tag), Some(msg.clone()));
+
+    if msg_len > 0 {
+        let mut forged_ct = ct.clone();
+        forged_ct[msg_len - 1] ^= 1;
+        assert_eq!(cyclist_open(key, nonce, &ad, &forged_ct, &tag), None);
+    }
+
+    let mut forged_ad = ad.clone();
+    forged_ad.push(0);
+    assert_eq!(cyclist_open(key, nonce, &forged_ad, &ct, &tag), None);
+
+    let mut forged_tag = tag;
+    forged_tag[msg_len % 16] ^= 0x80;
+    assert_eq!(cyclist_open(key, nonce, &ad, &ct, &forged_tag), None);
+
+    assert_eq!(cyclist_open(b"some other key", nonce, &ad, &ct, &tag), None);
+    assert_eq!(cyclist_open(key, b"other nonce", &ad, &ct, &tag), None);
+}
+
+#[test]
+fn cyclist_should_ratchet_and_squeeze_keys() {
+    let mut alice = Cyclist::keyed(b"shared secret key", b"id", b"counter");
+    let mut bob = alice.clone();
+
+    alice.ratchet();
+    bob.ratchet();
+
+    let mut alice_key = [0u8; 32];
+    let mut bob_key = [0u8; 32];
+    alice.squeeze_key(&mut alice_key);
+    bob.squeeze_key(&mut bob_key);
+    assert_eq!(alice_key, bob_key);
+
+    // Squeezing a key is domain separated from squeezing a tag, and ratcheting changes the state.
+    let mut tag = [0u8; 32];
+    let mut unratcheted_key = [0u8; 32];
+    Cyclist::keyed(b"shared secret key", b"id", b"counter").squeeze(&mut tag);
+    Cyclist::keyed(b"shared secret key", b"id", b"counter").squeeze_key(&mut unratcheted_key);
+    assert_ne!(tag, unratcheted_key);
+    assert_ne!(alice_key, unratcheted_key);
+
+    // Key identifier and counter are bound to the state.
+    let mut other_key = [0u8; 32];
+    Cyclist::keyed(b"shared secret key", b"di", b"counter").squeeze_key(&mut other_key);
+    assert_ne!(other_key, unratcheted_key);
+    Cyclist::keyed(b"shared secret key", b"id", b"").squeeze_key(&mut other_key);
+    assert_ne!(other_key, unratcheted_key);
+}
+
+#[test]
+#[should_panic]
+fn cyclist_should_not_encrypt_in_hash_mode() {
+    Cyclist::hash().encrypt(&mut [0u8; 16]);
+}
+
+/// Regression vectors, generated using this implementation, as there are no published vectors of Cyclist instantiated
+/// with Keccak-p[1600, 12] permutation.
+#[test]
+fn cyclist_should_match_regression_vectors() {
+    let mut digest = [0u8; 32];
+    let mut hasher = Cyclist::hash();
+    hasher.absorb(&ptn(17));
+    hasher.squeeze(&mut digest);
+    assert_eq!(const_hex::encode(digest), "c34342958291316fea5cf6b3a708706c0f480e30985d2711ca7ee088d03cbddb");
+
+    let (ct, tag) = cyclist_seal(b"turboshake::tests::cyclist::key", b"nonce", b"associated data", &ptn(200));
+    assert_eq!(
+        const_hex::encode(&ct),
+        "a38677a589fdef98cc90693487876cdfe43d36f84512911c33d670e46d06930c27d62b6a683e140e80ddcafa8cbecfbd16e417ac9a81b9e2cb2125e5f77edf4ceaac52a377b0b071373dbcef25ccff82defd9250995e463d1d2b9e8f0f782e57d0a32fee6914f291554acd22281d1ecb9029341bf6e38d27085a8fc9eefa8beafa708648d7f92e7c9544aca262b0f651ddc4eaa3979ba11230e806cf743457903ffe4ff39255921fe8e28f1b51775827b86930713d5fb45391981bdca2553f8e77a989813fa9704d"
+    );
+    assert_eq!(const_hex::encode(tag), "fb6b8e2b8312ad49d5a06de3e72b343d");
+}
+
+/// Duplex object over Keccak-p[1600, 12] with 168 -bytes rate, following algorithm 3 of "Duplexing the sponge"
+/// https://eprint.iacr.org/2011/499, where each duplexing call returns its output, for checking SpongeWrap against its
+/// specification.
+struct TestDuplex([u8; keccak::STATE_BYTE_LEN]);
+
+impl TestDuplex {
+    fn duplexing(&mut self, block: &[u8], frame_bit: u8, out_len: usize) -> Vec<u8> {
+        let mut padded = [0u8; 168];
+        padded[..block.len()].copy_from_slice(block);
+        padded[block.len()] = frame_bit | 0x02;
+        padded[167] ^= 0x80;
+
+        self.0.iter_mut().zip(padded).for_each(|(s, p)| *s ^= p);
+        keccak::permute_nr_bytes::<{ keccak::ROUNDS }>(&mut self.0);
+        self.0[..out_len].to_vec()
+    }
+}
+
+/// Computes SpongeWrap of a single (ad, msg) pair, following algorithm 4 of "Duplexing the sponge", returning ciphertext and tag.
+fn spongewrap_reference(key: &[u8], nonce: &[u8], ad: &[u8], msg: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    const RHO: usize = 167;
+
+    fn blocks(x: &[u8]) -> Vec<&[u8]> {
+        if x.is_empty() { vec![x] } else { x.chunks(RHO).collect() }
+    }
+
+    let mut duplex = TestDuplex([0u8; keccak::STATE_BYTE_LEN]);
+    for x in [key, nonce] {
+        let x_blocks = blocks(x);
+        for (i, block) in x_blocks.iter().enumerate() {
+            duplex.duplexing(block, (i + 1 < x_blocks.len()) as u8, 0);
+        }
+    }
+
+    let ad_blocks = blocks(ad);
+    let msg_blocks = blocks(msg);
+
+    let mut z = vec![];
+    for (i, block) in ad_blocks.iter().enumerate() {
+        z = duplex.duplexing(block, (i + 1 == ad_blocks.len()) as u8, msg_blocks[0].len());
+    }
+
+    let mut ct = vec![];
+    for (i, block) in msg_blocks.iter().enumerate() {
+        ct.extend(block.iter().zip(&z).map(|(b, k)| b ^ k));
+
+        let is_last = i + 1 == msg_blocks.len();
+        let out_len = if is_last { 16 } else { msg_blocks[i + 1].len() };
+        z = duplex.duplexing(block, (!is_last) as u8, out_len);
+    }
+
+    (ct, z)
+}
+
+#[test_case(0, 0; "empty associated data and message")]
+#[test_case(0, 1; "empty associated data and single byte message")]
+#[test_case(167, 166; "single block associated data and message")]
+#[test_case(168, 167; "two blocks associated data and single block message")]
+#[test_case(334, 168; "two blocks associated data and message")]
+#[test_case(17, 1000; "long message")]
+fn spongewrap_should_follow_its_specification(ad_len: usize, msg_len: usize) {
+    let key = b"turboshake::tests::spongewrap::key";
+    let nonce = ptn(200);
+    let ad = ptn(ad_len);
+    let msg = ptn(msg_len);
+
+    let (expected_ct, expected_tag) = spongewrap_reference(key, &nonce, &ad, &msg);
+
+    let mut ct = msg.clone();
+    let tag = SpongeWrap::new(key, &nonce).seal(&ad, &mut ct);
+
+    assert_eq!(ct, expected_ct);
+    assert_eq!(tag.to_vec(), expected_tag);
+}
+
+/// Session-based authenticated encryption scheme, so that behaviour common to SpongeWrap, Lake Keyak, Ketje Major and
+/// Kravatte-SANE/SANSE is tested using same helpers.
+trait TestAead: Clone {
+    const TAG_BYTE_LEN: usize;
+
+    fn seal(&mut self, ad: &[u8], data: &mut [u8]) -> Vec<u8>;
+    fn open(&mut self, ad: &[u8], data: &mut [u8], tag: &[u8]) -> Result<(), TurboShakeError>;
+}
+
+macro_rules! impl_test_aead {
+    ($($t:ty),+) => {
+        $(
+            impl TestAead for $t {
+                const TAG_BYTE_LEN: usize = <$t>::TAG_BYTE_LEN;
+
+                fn seal(&mut self, ad: &[u8], data: &mut [u8]) -> Vec<u8> {
+                    <$t>::seal(self, ad, data).to_vec()
+                }
+
+                fn open(&mut self, ad: &[u8], data: &mut [u8], tag: &[u8]) -> Result<(), TurboShakeError> {
+                    <$t>::open(self, ad, data, tag.try_into().expect("tag must be of expected length"))
+                }
+            }
+        )+
+    };
+}
+
+impl_test_aead!(SpongeWrap, LakeKeyak, KetjeMajor, KravatteSane, KravatteSanse);
+
+/// Test if a session, wrapping messages of given lengths, one after another, each with associated data of different length,
+/// can be unwrapped by a receiver in same state, while flipping a bit of ciphertext, associated data or tag gets it rejected,
+/// zeroing out the plaintext.
+fn test_aead_roundtrip_and_forgeries<A: TestAead>(mut sender: A, msg_lens: &[usize]) {
+    let mut receiver = sender.clone();
+
+    for (i, &msg_len) in msg_lens.iter().enumerate() {
+        let ad = ptn(i * 37);
+        let msg = ptn(msg_len);
+
+        let mut ct = msg.clone();
+        let tag = sender.seal(&ad, &mut ct);
+        assert_eq!(tag.len(), A::TAG_BYTE_LEN);
+        if msg_len > 0 {
+            assert_ne!(ct, msg);
+
+            let mut forged_ct = ct.clone();
+            forged_ct[(i * 7) % msg_len] ^= 1 << (i % 8);
+            assert_eq!(receiver.clone().open(&ad, &mut forged_ct, &tag), Err(TurboShakeError::AuthenticationFailed));
+            assert!(forged_ct.iter().all(|&byte| byte == 0));
+        }
+
+        let mut forged_ad = ad.clone();
+        forged_ad.push(0);
+        let mut pt = ct.clone();
+        assert_eq!(receiver.clone().open(&forged_ad, &mut pt, &tag), Err(TurboShakeError::AuthenticationFailed));
+        assert!(pt.iter().all(|&byte| byte == 0));
+
+        let mut forged_tag = tag.clone();
+        forged_tag[i % A::TAG_BYTE_LEN] ^= 0x80;
+        let mut pt = ct.clone();
+        assert_eq!(receiver.clone().open(&ad, &mut pt, &forged_tag), Err(TurboShakeError::AuthenticationFailed));
+
+        let mut pt = ct.clone();
+        assert_eq!(receiver.open(&ad, &mut pt, &tag), Ok(()));
+        assert_eq!(pt, msg);
+    }
+}
+
+/// Test if a session, which failed to open a forged message, refuses both sealing and opening afterwards.
+fn test_aead_refusing_use_after_failed_open<A: TestAead + std::panic::UnwindSafe>(mut aead: A) {
+    let tag = vec![0u8; A::TAG_BYTE_LEN];
+    assert_eq!(aead.open(b"", &mut [0u8; 16], &tag), Err(TurboShakeError::AuthenticationFailed));
+
+    let mut sealer = aead.clone();
+    assert!(std::panic::catch_unwind(move || sealer.seal(b"", &mut [0u8; 16])).is_err());
+    assert!(std::panic::catch_unwind(move || aead.open(b"", &mut [0u8; 16], &tag)).is_err());
+}
+
+/// Test if a session produces expected ciphertexts and tags, when sealing each (associated data, ptn(message length)) pair,
+/// one after another, while a receiver in same state opens them.
+fn test_aead_known_answers<A: TestAead>(mut sender: A, vectors: &[(&[u8], usize, &str, &str)]) {
+    let mut receiver = sender.clone();
+
+    for &(ad, msg_len, expected_ct, expected_tag) in vectors {
+        let mut ct = ptn(msg_len);
+        let tag = sender.seal(ad, &mut ct);
+
+        assert_eq!(const_hex::encode(&ct), expected_ct);
+        assert_eq!(const_hex::encode(&tag), expected_tag);
+
+        assert_eq!(receiver.open(ad, &mut ct, &tag), Ok(()));
+        assert_eq!(ct, ptn(msg_len));
+    }
+}
+
+#[test]
+fn aead_sessions_should_refuse_use_after_failed_open() {
+    test_aead_refusing_use_after_failed_open(SpongeWrap::new(b"shared secret key", b"nonce"));
+    test_aead_refusing_use_after_failed_open(LakeKeyak::new(b"shared secret key", b"nonce"));
+    test_aead_refusing_use_after_failed_open(KetjeMajor::new(b"shared secret key", b"nonce"));
+    test_aead_refusing_use_after_failed_open(KravatteSane::new(b"shared secret key", b"nonce").0);
+    test_aead_refusing_use_after_failed_open(KravatteSanse::new(b"shared secret key"));
+}
+
+#[test]
+fn spongewrap_should_roundtrip_sessions_and_reject_forgeries() {
+    test_aead_roundtrip_and_forgeries(SpongeWrap::new(b"shared secret key", b"nonce"), &[0, 1, 166, 167, 168, 500]);
+
+    // Tags depend on key and nonce, along with all previously wrapped messages.
+    let mut sender = SpongeWrap::new(b"shared secret key", b"nonce");
+    let mut ct = ptn(32);
+    let tag = sender.seal(b"", &mut ct);
+
+    let mut pt = ct.clone();
+    assert!(SpongeWrap::new(b"shared secret key", b"other nonce").open(b"", &mut pt, &tag).is_err());
+    let mut pt = ct.clone();
+    assert!(SpongeWrap::new(b"other secret key", b"nonce").open(b"", &mut pt, &tag).is_err());
+    let mut pt = ct.clone();
+    assert!(sender.open(b"", &mut pt, &tag).is_err());
+}
+
+/// Regression vectors, generated using this implementation, as there are no published vectors of SpongeWrap instantiated
+/// with Keccak-p[1600, 12] permutation.
+#[test]
+fn spongewrap_should_match_regression_vectors() {
+    test_aead_known_answers(
+        SpongeWrap::new(b"turboshake::tests::spongewrap::key", b"nonce"),
+        &[
+            (
+                b"associated data",
+                200,
+                "b2dc31d11a6cee700341f5018acfc259deb8b552a1a7ab72d749ac0304294ed04c1ba6b9a51ae2895dff4e8467c5f82855b86660a7b26f91561a66f3151c3515b7b97a8d11d70023d9143dcee2236e5604a4019fa5313d1ef6cd0022fec42997484e9bd5307f3bb539891cfd8e77cfd2d10bf68f5e15baab23945efe16e7d28a91e29ef2a4d51e7179175814b74a90f4a6e82ba457ebedd0c99054bd833ae9b33e5e4319cfd9e0b0a6f4d18d015d8a6a5762a7604b5646002e0e0c089b9d6dff7bfc78eb4a4db6e1",
+                "b571072301d6fc51c2a01ba2909d8568",
+            ),
+            (b"", 17, "c497eb50a35b3c9ef3e7900a8103df238a", "64ddf00eca29facfd0bbb8eae5e6695b"),
+        ],
+    );
+}
+
+/// Lake Keyak, written as a direct transcription of Motorist mode of Keyak v2 specification, pulling input bytes one at a
+/// time, for checking the layered implementation, which processes slices, against it. It returns ciphertexts and tags of a
+/// session, wrapping each (ad, msg) pair.
+fn lake_keyak_reference(key: &[u8], nonce: &[u8], wraps: &[(Vec<u8>, Vec<u8>)]) -> Vec<(Vec<u8>, Vec<u8>)> {
+    const RS: usize = 168;
+    const RA: usize = 192;
+
+    struct Reference {
+        s: [u8; keccak::STATE_BYTE_LEN],
+        et: usize,
+    }
+
+    impl Reference {
+        fn spark(&mut self, eom: bool, l: usize) {
+            if eom {
+                self.s[RA] ^= if l == 0 { 0xff } else { l as u8 };
+            }
+            keccak::permute_nr_bytes::<{ keccak::ROUNDS }>(&mut self.s);
+            self.et = l;
+        }
+
+        fn inject(&mut self, x: &mut std::collections::VecDeque<u8>, crypting: bool) {
+            let mut omega = if crypting { RS } else { 0 };
+            self.s[RA + 2] ^= omega as u8;
+            while !x.is_empty() && omega < RA {
+                self.s[omega] ^= x.pop_front().unwrap();
+                omega += 1;
+            }
+            self.s[RA + 3] ^= omega as u8;
+        }
+
+        fn get_tag(&mut self, l: usize) -> Vec<u8> {
+            self.spark(true, l);
+            self.s[..l].to_vec()
+        }
+    }
+
+    let mut suv = vec![40u8];
+    suv.extend_from_slice(key);
+    suv.push(0x01);
+    suv.resize(40, 0);
+    suv.extend_from_slice(nonce);
+    suv.extend_from_slice(&[1, 0]);
+
+    let mut r = Reference {
+        s: [0u8; keccak::STATE_BYTE_LEN],
+        et: 0,
+    };
+
+    let mut x = suv.into_iter().collect();
+    r.inject(&mut x, false);
+    while !x.is_empty() {
+        r.spark(false, 0);
+        r.inject(&mut x, false);
+    }
+    r.get_tag(0);
+
+    wraps
+        .iter()
+        .map(|(ad, msg)| {
+            let mut a = ad.iter().copied().collect();
+            let mut i: std::collections::VecDeque<u8> = msg.iter().copied().collect();
+            let mut o = vec![];
+
+            while !i.is_empty() {
+                let mut omega = r.et;
+                while !i.is_empty() && omega < RS {
+                    r.s[omega] ^= i.pop_front().unwrap();
+                    o.push(r.s[omega]);
+                    omega += 1;
+                }
+                r.s[RA + 1] ^= omega as u8;
+
+                r.inject(&mut a, true);
+                if !i.is_empty() || !a.is_empty() {
+                    r.spark(false, 0);
+                }
+            }
+            while !a.is_empty() {
+                r.inject(&mut a, false);
+                if !a.is_empty() {
+                    r.spark(false, 0);
+                }
+            }
+
+            (o, r.get_tag(16))
+        })
+        .collect()
+}
+
+#[test]
+fn lake_keyak_should_follow_its_specification() {
+    let key = ptn(16);
+    let nonce = ptn(150);
+
+    let wraps: Vec<(Vec<u8>, Vec<u8>)> = [
+        (0, 0),
+        (0, 1),
+        (1, 0),
+        (24, 168),
+        (25, 169),
+        (500, 100),
+        (10, 1000),
+        (0, 0),
+        (192, 0),
+        (193, 167),
+    ]
+    .into_iter()
+    .map(|(ad_len, msg_len)| (ptn(ad_len), ptn(msg_len)))
+    .collect();
+
+    let expected = lake_keyak_reference(&key, &nonce, &wraps);
+
+    let mut keyak = LakeKeyak::new(&key, &nonce);
+    for ((ad, msg), (expected_ct, expected_tag)) in wraps.iter().zip(expected) {
+        let mut ct = msg.clone();
+        let tag = keyak.seal(ad, &mut ct);
+
+        assert_eq!(ct, expected_ct);
+        assert_eq!(tag.to_vec(), expected_tag);
+    }
+}
+
+#[test]
+fn lake_keyak_should_roundtrip_sessions_and_reject_forgeries() {
+    test_aead_roundtrip_and_forgeries(LakeKeyak::new(b"shared secret key", b"session nonce"), &[0, 1, 167, 168, 169, 336, 1000]);
+
+    // Tags depend on key and nonce.
+    let mut ct = ptn(32);
+    let tag = LakeKeyak::new(b"shared secret key", b"nonce").seal(b"", &mut ct);
+
+    let mut pt = ct.clone();
+    assert!(LakeKeyak::new(b"shared secret key", b"other nonce").open(b"", &mut pt, &tag).is_err());
+    let mut pt = ct.clone();
+    assert!(LakeKeyak::new(b"other secret key", b"nonce").open(b"", &mut pt, &tag).is_err());
+}
+
+/// Regression vectors, generated using this implementation. Published Lake Keyak test vectors aren't available in this
+/// source tree, so conformance with them is yet to be checked.
+#[test]
+fn lake_keyak_should_match_regression_vectors() {
+    test_aead_known_answers(
+        LakeKeyak::new(&ptn(16), &ptn(16)),
+        &[
+            (
+                b"associated data",
+                200,
+                "362b3df4bbbfa5905d29186b34692abf7a4eb83e07b30b336979b5eff98a1998ee573c8a45865e7e14296dd73448f95a5b2a6a1c761150bfb9cc75cf5dcdcd417d3e795f9d827199613e449017aede8f82f6f11152c1c82d24997a8f583f2277281208b7b9e1f1e875c9e8661ee38f9bdcbbb6f90319fe7d78aa1e0bc73cd03046a0d92cd32f3e9781cb4d7d180a69e2e9a417d3218b8b792374aeb4afcb2b25a2eb7e2a56d11ce69b3daf2156b56382eda06c4022b7ff59fd237ce22a823e1855a8352d00325d62",
+                "8ff6b08fbb4018013e3c20c7a7a4ea5d",
+            ),
+            (b"", 17, "b7070b0048caba5ba1ae97f8247953d7c3", "c9ec356f3acf44a66046fb90298392ac"),
+        ],
+    );
+}
+
+/// MonkeyDuplex over twisted Keccak-p[1600], with 260 -bits duplex rate, following section 2.3 of Ketje v2 specification
+/// https://keccak.team/ketje.html, where input is a string of bits, for checking byte-oriented Ketje Major against it.
+struct TestMonkeyDuplex([u64; keccak::LANE_CNT]);
+
+impl TestMonkeyDuplex {
+    /// Applies π ∘ Keccak-p[1600, NUM_ROUNDS] ∘ π⁻¹, where π⁻¹ is computed as π²³, as π cycles through 24 lanes.
+    fn permute<const NUM_ROUNDS: usize>(&mut self) {
+        (0..23).for_each(|_| keccak::pi(&mut self.0));
+        keccak::permute_nr::<NUM_ROUNDS>(&mut self.0);
+        keccak::pi(&mut self.0);
+    }
+
+    /// Adds `bits` padded with pad10*1 to `rate_bits`, to the state.
+    fn add_padded(&mut self, bits: &[bool], rate_bits: usize) {
+        let mut padded = bits.to_vec();
+        padded.push(true);
+        padded.resize(rate_bits - 1, false);
+        padded.push(true);
+
+        padded
+            .iter()
+            .enumerate()
+            .filter(|(_, bit)| **bit)
+            .for_each(|(i, _)| self.0[i / 64] ^= 1 << (i % 64));
+    }
+
+    fn duplex<const NUM_ROUNDS: usize>(&mut self, sigma: &[bool], out_len: usize) -> Vec<u8> {
+        self.add_padded(sigma, 260);
+        self.permute::<NUM_ROUNDS>();
+        self.0.iter().flat_map(|lane| lane.to_le_bytes()).take(out_len).collect()
+    }
+}
+
+fn to_bits(bytes: &[u8], frame_bits: [bool; 2]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|&byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+        .chain(frame_bits)
+        .collect()
+}
+
+/// Ketje Major, following MonkeyWrap algorithm of Ketje v2 specification, returning ciphertexts and tags of a session,
+/// wrapping each (ad, msg) pair.
+fn ketje_major_reference(key: &[u8], nonce: &[u8], wraps: &[(Vec<u8>, Vec<u8>)]) -> Vec<(Vec<u8>, Vec<u8>)> {
+    fn blocks(x: &[u8]) -> Vec<&[u8]> {
+        if x.is_empty() { vec![x] } else { x.chunks(32).collect() }
+    }
+
+    let mut keypack = vec![key.len() as u8 + 2];
+    keypack.extend_from_slice(key);
+    keypack.push(0x01);
+    keypack.extend_from_slice(nonce);
+
+    let mut d = TestMonkeyDuplex([0u64; keccak::LANE_CNT]);
+    d.add_padded(&to_bits(&keypack, [false; 2])[..keypack.len() * 8], 1600);
+    d.permute::<12>();
+
+    wraps
+        .iter()
+        .map(|(ad, msg)| {
+            let ad_blocks = blocks(ad);
+            let msg_blocks = blocks(msg);
+
+            for block in &ad_blocks[..ad_blocks.len() - 1] {
+                d.duplex::<1>(&to_bits(block, [false, false]), 0);
+            }
+            let mut z = d.duplex::<1>(&to_bits(ad_blocks[ad_blocks.len() - 1], [false, true]), msg_blocks[0].len());
+
+            let mut ct = vec![];
+            for (i, block) in msg_blocks.iter().enumerate() {
+                ct.extend(block.iter().zip(&z).map(|(b, k)| b ^ k));
+
+                if i + 1 < msg_blocks.len() {
+                    z = d.duplex::<1>(&to_bits(block, [true, true]), msg_blocks[i + 1].len());
+                } else {
+                    z = d.duplex::<6>(&to_bits(block, [true, false]), 16);
+                }
+            }
+
+            (ct, z)
+        })
+        .collect()
+}
+
+#[test]
+fn ketje_major_should_follow_its_specification() {
+    let key = ptn(16);
+    let nonce = ptn(50);
+
+    let wraps: Vec<(Vec<u8>, Vec<u8>)> = [(0, 0), (0, 1), (1, 0), (31, 32), (32, 33), (33, 64), (100, 1000), (0, 0), (64, 0)]
+        .into_iter()
+        .map(|(ad_len, msg_len)| (ptn(ad_len), ptn(msg_len)))
+        .collect();
+
+    let expected = ketje_major_reference(&key, &nonce, &wraps);
+
+    let mut ketje = KetjeMajor::new(&key, &nonce);
+    for ((ad, msg), (expected_ct, expected_tag)) in wraps.iter().zip(expected) {
+        let mut ct = msg.clone();
+        let tag = ketje.seal(ad, &mut ct);
+
+        assert_eq!(ct, expected_ct);
+        assert_eq!(tag.to_vec(), expected_tag);
+    }
+}
+
+#[test]
+fn ketje_major_should_roundtrip_sessions_and_reject_forgeries() {
+    test_aead_roundtrip_and_forgeries(KetjeMajor::new(b"shared secret key", b"session nonce"), &[0, 1, 31, 32, 33, 64, 1000]);
+
+    // Tags depend on key and nonce.
+    let mut ct = ptn(32);
+    let tag = KetjeMajor::new(b"shared secret key", b"nonce").seal(b"", &mut ct);
+
+    let mut pt = ct.clone();
+    assert!(KetjeMajor::new(b"shared secret key", b"other nonce").open(b"", &mut pt, &tag).is_err());
+    let mut pt = ct.clone();
+    assert!(KetjeMajor::new(b"other secret key", b"nonce").open(b"", &mut pt, &tag).is_err());
+}
+
+/// Regression vectors, generated using this implementation. CAESAR submission's Ketje Major test vectors aren't available in
+/// this source tree, so conformance with them is yet to be checked.
+#[test]
+fn ketje_major_should_match_regression_vectors() {
+    test_aead_known_answers(
+        KetjeMajor::new(&ptn(16), &ptn(16)),
+        &[
+            (
+                b"associated data",
+                100,
+                "2ea8a1104873f87696ad83c6ae51659450abd4a96f24f15506ccd718a0b77bce67bda9aebac01f56a4e84a286234097e3e400e46b645625f799e9858e0e8e0d64ef789f4674d108ee3291573f47df311fd531443e79f29df523de8e6e090f92aca3087da",
+                "4acea8fcf63225e6a93c1b62ce66c7b9",
+            ),
+            (b"", 17, "3baf04f8c50685fcd14273d3c7ea08ebd5", "51ac1d746651247f52b64b7efa9a574b"),
+        ],
+    );
+}
+
+#[test]
+fn kravatte_should_expand_consistently_from_any_offset() {
+    let mut kravatte = Kravatte::new(b"turboshake::tests::kravatte::key");
+    kravatte.compress(&ptn(1000));
+
+    let mut full_out = [0u8; 1000];
+    kravatte.expand(0, &mut full_out);
+
+    for (offset, len) in [(0, 1), (1, 199), (16, 100), (199, 2), (200, 200), (201, 399), (599, 401)] {
+        let mut out = vec![0u8; len];
+        kravatte.expand(offset, &mut out);
+        assert_eq!(out, full_out[offset..offset + len]);
+    }
+}
+
+#[test]
+fn kravatte_should_compress_sequences_of_strings_incrementally() {
+    let key = b"turboshake::tests::kravatte::key";
+
+    let expand = |strings: &[&[u8]]| {
+        let mut kravatte = Kravatte::new(key);
+        strings.iter().for_each(|string| kravatte.compress(string));
+
+        let mut out = [0u8; 32];
+        kravatte.expand(0, &mut out);
+        out
+    };
+
+    // Strings are padded, s.t. splitting a string or appending an empty string changes output, including at block boundary.
+    let long_string = ptn(400);
+    let outputs = [
+        expand(&[]),
+        expand(&[b""]),
+        expand(&[b"", b""]),
+        expand(&[b"turboshake"]),
+        expand(&[b"turbo", b"shake"]),
+        expand(&[b"turboshake", b""]),
+        expand(&[&long_string]),
+        expand(&[&long_string[..200], &long_string[200..]]),
+        expand(&[&long_string[..199]]),
+        expand(&[&long_string[..200]]),
+    ];
+
+    for i in 0..outputs.len() {
+        for j in (i + 1)..outputs.len() {
+            assert_ne!(outputs[i], outputs[j], "outputs {i} and {j} must differ");
+        }
+    }
+
+    // Compressing a string, after expanding output of its prefix, is same as compressing the sequence at once.
+    let mut kravatte = Kravatte::new(key);
+    kravatte.compress(b"turbo");
+    let mut prefix_out = [0u8; 32];
+    kravatte.expand(0, &mut prefix_out);
+    kravatte.compress(b"shake");
+
+    let mut out = [0u8; 32];
+    kravatte.expand(0, &mut out);
+    assert_eq!(out, expand(&[b"turbo", b"shake"]));
+
+    let mut other_key_out = [0u8; 32];
+    let mut kravatte = Kravatte::new(b"other key");
+    kravatte.compress(b"turbo");
+    kravatte.compress(b"shake");
+    kravatte.expand(0, &mut other_key_out);
+    assert_ne!(out, other_key_out);
+}
+
+#[test]
+fn kravatte_sane_should_roundtrip_sessions_and_reject_forgeries() {
+    let (sender, sender_tag) = KravatteSane::new(b"shared secret key", b"session nonce");
+    let (_, receiver_tag) = KravatteSane::new(b"shared secret key", b"session nonce");
+    assert_eq!(sender_tag, receiver_tag);
+    assert_ne!(KravatteSane::new(b"shared secret key", b"other nonce").1, sender_tag);
+
+    test_aead_roundtrip_and_forgeries(sender, &[0, 1, 183, 184, 200, 1000]);
+}
+
+#[test]
+fn kravatte_sanse_should_roundtrip_sessions_and_reject_forgeries() {
+    test_aead_roundtrip_and_forgeries(KravatteSanse::new(b"shared secret key"), &[0, 1, 199, 200, 1000]);
+
+    // Without a nonce, same message with same history gets same ciphertext, while messages differing in a single bit get
+    // unrelated ciphertexts and tags.
+    let mut ct0 = ptn(64);
+    let mut ct1 = ptn(64);
+    let mut ct2 = ptn(64);
+    ct2[63] ^= 1;
+
+    let tag0 = KravatteSanse::new(b"shared secret key").seal(b"ad", &mut ct0);
+    let tag1 = KravatteSanse::new(b"shared secret key").seal(b"ad", &mut ct1);
+    let tag2 = KravatteSanse::new(b"shared secret key").seal(b"ad", &mut ct2);
+
+    assert_eq!((ct0.clone(), tag0), (ct1, tag1));
+    assert_ne!(ct0[..32], ct2[..32]);
+    assert_ne!(tag0, tag2);
+}
+
+/// Regression vectors, generated using this implementation. Keccak team's published Kravatte vectors aren't available in
+/// this source tree, so conformance with them is yet to be checked.
+#[test]
+fn kravatte_should_match_regression_vectors() {
+    let mut kravatte = Kravatte::new(&ptn(32));
+    kravatte.compress(&ptn(250));
+
+    let mut out = [0u8; 48];
+    kravatte.expand(0, &mut out);
+    assert_eq!(
+        const_hex::encode(out),
+        "7fbd8cae00f1e9425c4400d9fbb1789e50a0c428f01baa214e7bafa7fa73f0b53c9b51faa27eba1eb6481b607d830162"
+    );
+
+    let (sane, tag) = KravatteSane::new(&ptn(32), &ptn(16));
+    assert_eq!(const_hex::encode(tag), "4916e97a7f56315e120793d754d81e36");
+    test_aead_known_answers(
+        sane,
+        &[(
+            b"associated data",
+            33,
+            "de5668bb910e4b963b53b3c7af7ad242c9bb189aef482dba8dbacb773b60604335",
+            "fbfd9aa5f288f3711ba39dc4a5c05069",
+        )],
+    );
+
+    test_aead_known_answers(
+        KravatteSanse::new(&ptn(32)),
+        &[(
+            b"associated data",
+            33,
+            "887333ff2093a023496cb21dc1ea9fb721bf499b2521a6ced588ea99c5f39b77f3",
+            "211146b40d7c9ce791a869d3771ca36fe1d62023dd0db44e7ae19179866b7bd6",
+        )],
+    );
+}
+
+/// Returns a TurboSHAKE128 reader, seeded with random bytes, to be used as source of fresh randomness for masking.
+fn masking_rng() -> TurboShake128Reader {
+    let mut seed = [0u8; 32];
+    rand::rng().fill(&mut seed);
+
+    let mut hasher = TurboShake128::default();
+    assert_eq!(hasher.absorb(&seed), Ok(()));
+    hasher.fork_finalize::<{ TurboShake128::DEFAULT_DOMAIN_SEPARATOR }>().expect("must fork")
+}
+
+/// Test if masked Keccak-p\[1600, 12\] permutation, applied on a random sharing of a random state, produces a sharing of
+/// the state, permuted using unmasked Keccak-p\[1600, 12\] permutation.
+fn test_permute_masked<const SHARES: usize>() {
+    const ITERATION_COUNT: usize = 100;
+
+    let mut rng = rand::rng();
+    let mut masking_rng = masking_rng();
+
+    for _ in 0..ITERATION_COUNT {
+        let mut state = [0u64; keccak::LANE_CNT];
+        rng.fill(&mut state);
+
+        let mut shares = [[0u64; keccak::LANE_CNT]; SHARES];
+        shares[0] = state;
+        for share_idx in 1..SHARES {
+            let mut share = [0u64; keccak::LANE_CNT];
+            rng.fill(&mut share);
+
+            shares[share_idx] = share;
+            shares[0].iter_mut().zip(share).for_each(|(lane, r)| *lane ^= r);
+        }
+
+        keccak::permute(&mut state);
+        keccak::permute_masked(&mut shares, || sampling::u64(&mut masking_rng));
+
+        let recombined: [u64; keccak::LANE_CNT] = std::array::from_fn(|lane_idx| shares.iter().fold(0, |acc, share| acc ^ share[lane_idx]));
+        assert_eq!(recombined, state);
+    }
+}
+
+/// Test if masked TurboSHAKE128 produces same output as of unmasked TurboSHAKE128, for messages, partly absorbed as shares,
+/// and outputs spanning multiple blocks, while it rejects out of order calls.
+fn test_masked_turboshake128<const SHARES: usize>() {
+    for (mlen, dlen) in [(0, 32), (1, 1), (167, 168), (168, 169), (1000, 500)] {
+        let msg = ptn(mlen);
+
+        let mut expected = vec![0u8; dlen];
+        assert_eq!(turboshake128::<0x06>(&msg).squeeze(&mut expected), Ok(()));
+
+        let mut masked = MaskedTurboShake128::<_, SHARES>::new(masking_rng());
+        assert_eq!(masked.squeeze(&mut [0u8; 1]), Err(TurboShakeError::StillInDataAbsorptionPhase));
+
+        // Secret portion of the message is absorbed as a random sharing of it, while rest of it is absorbed unmasked.
+        let split_at = mlen / 3;
+        let mut shares = [(); SHARES].map(|_| vec![0u8; split_at]);
+        shares[0].copy_from_slice(&msg[..split_at]);
+        for share_idx in 1..SHARES {
+            rand::rng().fill(&mut shares[share_idx][..]);
+
+            let share = shares[share_idx].clone();
+            shares[0].iter_mut().zip(share).for_each(|(byte, r)| *byte ^= r);
+        }
+
+        assert_eq!(masked.absorb_shares(core::array::from_fn(|i| &shares[i][..])), Ok(()));
+        assert_eq!(masked.absorb(&msg[split_at..]), Ok(()));
+        assert_eq!(masked.finalize::<0x06>(), Ok(()));
+        assert_eq!(masked.absorb(&msg), Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized));
+        assert_eq!(
+            masked.absorb_shares([&msg[..0]; SHARES]),
+            Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized)
+        );
+        assert_eq!(masked.finalize::<0x06>(), Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized));
+
+        let mut computed = vec![0u8; dlen];
+        let split_at = dlen / 2;
+        assert_eq!(masked.squeeze(&mut computed[..split_at]), Ok(()));
+        assert_eq!(masked.squeeze(&mut computed[split_at..]), Ok(()));
+
+        assert_eq!(computed, expected);
+    }
+}
+
+#[test]
+fn masked_keccak_permutation_and_turboshake128_should_match_unmasked() {
+    test_permute_masked::<2>();
+    test_masked_turboshake128::<2>();
+    test_permute_masked::<3>();
+    test_masked_turboshake128::<3>();
+    test_permute_masked::<4>();
+    test_masked_turboshake128::<4>();
+    test_permute_masked::<5>();
+    test_masked_turboshake128::<5>();
+    test_permute_masked::<6>();
+    test_masked_turboshake128::<6>();
+    test_permute_masked::<7>();
+    test_masked_turboshake128::<7>();
+    test_permute_masked::<8>();
+    test_masked_turboshake128::<8>();
+}
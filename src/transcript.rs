@@ -0,0 +1,138 @@
+//! Merlin-style transcripts https://merlin.cool, for non-interactive zero-knowledge proofs using Fiat-Shamir transform,
+//! built on STROBE-128, with Keccak-p\[1600, 12\] permutation in place of Keccak-f\[1600\].
+//!
+//! Each message is appended as `meta-AD(label || LE32(len))`, followed by `AD(message)`, while each challenge is derived
+//! as `meta-AD(label || LE32(len))`, followed by `PRF(len)`. As STROBE frames each operation, by absorbing where previous
+//! one began along with its flags, and each label is followed by length of the data, a sequence of appended messages and
+//! derived challenges is unambiguously encoded. Instantiated with 24 rounds, i.e. `Transcript::<24>`, it's compatible with
+//! Merlin v3.0.0.
+//!
+//! ```
+//! use turboshake::Transcript;
+//!
+//! let mut prover = Transcript::new(b"my proof system");
+//! prover.append_message(b"commitment", b"prover's commitment");
+//!
+//! let mut verifier = prover.clone();
+//!
+//! let mut challenge = [0u8; 32];
+//! prover.challenge_bytes(b"challenge", &mut challenge);
+//!
+//! let mut expected_challenge = [0u8; 32];
+//! verifier.challenge_bytes(b"challenge", &mut expected_challenge);
+//! assert_eq!(challenge, expected_challenge);
+//! ```
+
+use crate::{keccak, sampling::XofReader, strobe::Strobe};
+
+/// Protocol label of STROBE-128 instance, underlying each transcript.
+const MERLIN_PROTOCOL_LABEL: &[u8] = b"Merlin v1.0";
+
+/// Encodes byte length of a message, as little-endian 32 -bit integer.
+fn encode_len(len: usize) -> [u8; 4] {
+    u32::try_from(len).expect("message must not be longer than 2^32 - 1 bytes").to_le_bytes()
+}
+
+/// Transcript of a public-coin argument, on top of STROBE-128 with Keccak-p\[1600, NUM_ROUNDS\] permutation. Cloning it is
+/// as cheap as copying 200 -bytes state, which lets protocols fork a transcript, say for deriving challenges of several
+/// sub-protocols from a common prefix.
+#[derive(Clone)]
+pub struct Transcript<const NUM_ROUNDS: usize = { keccak::ROUNDS }> {
+    strobe: Strobe<128, NUM_ROUNDS>,
+}
+
+impl Transcript {
+    /// Creates a new transcript, using Keccak-p\[1600, 12\] permutation, for a protocol identified by `label`.
+    pub fn new(label: &'static [u8]) -> Self {
+        Self::new_nr(label)
+    }
+}
+
+impl<const NUM_ROUNDS: usize> Transcript<NUM_ROUNDS> {
+    /// Creates a new transcript, using Keccak-p\[1600, NUM_ROUNDS\] permutation, for a protocol identified by `label`.
+    pub fn new_nr(label: &'static [u8]) -> Self {
+        let mut transcript = Self {
+            strobe: Strobe::new(MERLIN_PROTOCOL_LABEL),
+        };
+
+        transcript.append_message(b"dom-sep", label);
+        transcript
+    }
+
+    /// Appends `message` to the transcript, under `label`.
+    pub fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.strobe.meta_ad(label, false);
+        self.strobe.meta_ad(&encode_len(message.len()), true);
+        self.strobe.ad(message, false);
+    }
+
+    /// Appends `x` to the transcript, under `label`, as little-endian 64 -bit integer.
+    pub fn append_u64(&mut self, label: &'static [u8], x: u64) {
+        self.append_message(label, &x.to_le_bytes());
+    }
+
+    /// Fills `out` with challenge bytes, derived from the transcript, under `label`. Derived challenge is bound to its
+    /// length, so challenges of different lengths are unrelated.
+    pub fn challenge_bytes(&mut self, label: &'static [u8], out: &mut [u8]) {
+        self.strobe.meta_ad(label, false);
+        self.strobe.meta_ad(&encode_len(out.len()), true);
+        self.strobe.prf(out, false);
+    }
+
+    /// Forks the transcript, s.t. the fork is bound to `label`, while this transcript stays untouched.
+    pub fn fork(&self, label: &'static [u8]) -> Self {
+        let mut fork = self.clone();
+        fork.append_message(b"fork", label);
+        fork
+    }
+
+    /// Starts building a random number generator, bound to current state of the transcript, for generating blinding factors
+    /// and nonces of the prover. It's rekeyed with prover's secret witness and fresh randomness, so that it stays secure,
+    /// even if one of them is weak.
+    pub fn build_rng(&self) -> TranscriptRngBuilder<NUM_ROUNDS> {
+        TranscriptRngBuilder { strobe: self.strobe.clone() }
+    }
+}
+
+/// Builder of a random number generator, bound to a transcript, which gets rekeyed with witness bytes.
+pub struct TranscriptRngBuilder<const NUM_ROUNDS: usize = { keccak::ROUNDS }> {
+    strobe: Strobe<128, NUM_ROUNDS>,
+}
+
+impl<const NUM_ROUNDS: usize> TranscriptRngBuilder<NUM_ROUNDS> {
+    /// Rekeys random number generator with secret `witness`, under `label`.
+    pub fn rekey_with_witness_bytes(mut self, label: &'static [u8], witness: &[u8]) -> Self {
+        self.strobe.meta_ad(label, false);
+        self.strobe.meta_ad(&encode_len(witness.len()), true);
+        self.strobe.key(witness, false);
+        self
+    }
+
+    /// Rekeys random number generator with 32 -bytes fresh randomness, say sampled from operating system's random number
+    /// generator, finishing building it.
+    pub fn finalize(mut self, random_bytes: &[u8; 32]) -> TranscriptRng<NUM_ROUNDS> {
+        self.strobe.meta_ad(b"rng", false);
+        self.strobe.key(random_bytes, false);
+
+        TranscriptRng { strobe: self.strobe }
+    }
+}
+
+/// Random number generator, bound to a transcript, prover's witness and fresh randomness.
+pub struct TranscriptRng<const NUM_ROUNDS: usize = { keccak::ROUNDS }> {
+    strobe: Strobe<128, NUM_ROUNDS>,
+}
+
+impl<const NUM_ROUNDS: usize> TranscriptRng<NUM_ROUNDS> {
+    /// Fills `out` with random bytes.
+    pub fn fill_bytes(&mut self, out: &mut [u8]) {
+        self.strobe.meta_ad(&encode_len(out.len()), false);
+        self.strobe.prf(out, false);
+    }
+}
+
+impl<const NUM_ROUNDS: usize> XofReader for TranscriptRng<NUM_ROUNDS> {
+    fn squeeze(&mut self, out: &mut [u8]) {
+        self.fill_bytes(out);
+    }
+}
@@ -13,6 +13,7 @@ pub struct TurboShake256 {
     offset: usize,
     is_ready_to_squeeze: usize,
     squeezable: usize,
+    position: u64,
 }
 
 impl Default for TurboShake256 {
@@ -39,6 +40,7 @@ impl Default for TurboShake256 {
             offset: 0,
             is_ready_to_squeeze: usize::MIN,
             squeezable: 0,
+            position: 0,
         }
     }
 }
@@ -53,6 +55,31 @@ impl TurboShake256 {
     const RATE_BITS: usize = Self::BIT_LENGTH_OF_KECCAK_PERMUTATION_STATE - Self::CAPACITY_BITS;
     const RATE_BYTES: usize = Self::RATE_BITS / u8::BITS as usize;
 
+    /// Creates an instance of TurboSHAKE256, which has already absorbed `prefix`, say a key and a label. Further absorption
+    /// resumes from wherever the prefix ends, even if it's in the middle of a block. When hashing many messages, each prefixed
+    /// with it, either clone this instance for each message, or use `TurboShake256Prefix`, which can reset an instance back to
+    /// the prefix, so that the prefix needs to be absorbed only once.
+    ///
+    /// # Inputs
+    ///
+    /// * `prefix`: An arbitrary length (including empty) slice of bytes to be absorbed.
+    ///
+    /// # Returns
+    ///
+    /// A `TurboShake256` object, which has absorbed `prefix`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::TurboShake256;
+    ///
+    /// let mut ts = TurboShake256::with_prefix(b"key and label");
+    /// assert_eq!(ts.absorb(b"message"), Ok(()));
+    /// ```
+    pub fn with_prefix(prefix: &[u8]) -> Self {
+        Self::from(TurboShake256Prefix::new(prefix))
+    }
+
     /// Absorbs arbitrary many input bytes into the TurboSHAKE256 sponge state.
     /// It can be called as many times needed, as long as `finalize` has not been called.
     ///
@@ -63,7 +90,7 @@ impl TurboShake256 {
     /// # Returns
     ///
     /// * `Result<(), TurboShakeError>`: `Ok(())` if the absorption was successful.
-    /// Returns `Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized)` if the instance has already been finalized.
+    ///   Returns `Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized)` if the instance has already been finalized.
     ///
     /// # Example
     ///
@@ -93,7 +120,7 @@ impl TurboShake256 {
     /// # Returns
     ///
     /// * `Result<(), TurboShakeError>`: `Ok(())` if the finalization was successful.
-    /// Returns `Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized)` if the instance has already been finalized.
+    ///   Returns `Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized)` if the instance has already been finalized.
     ///
     /// # Example
     ///
@@ -121,6 +148,56 @@ impl TurboShake256 {
         Ok(())
     }
 
+    /// Finalizes a copy of the TurboSHAKE256 sponge state, using domain separator `D`, returning a reader, which can be used
+    /// for squeezing output. The instance itself is left untouched, so it can be forked again, with another domain separator,
+    /// or it can keep absorbing. Handy for deriving several domain-separated outputs, say keys and nonces, from a long message,
+    /// which is absorbed only once.
+    ///
+    /// # Inputs
+    ///
+    /// * `D`: A domain separator byte. Consider using `TurboShake256::DEFAULT_DOMAIN_SEPARATOR` if you don't need multiple instances of TurboSHAKE256.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TurboShake256Reader, TurboShakeError>`: Reader of TurboSHAKE256 output, if forking was successful.
+    ///   Returns `Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized)` if the instance has already been finalized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::TurboShake256;
+    ///
+    /// let mut ts = TurboShake256::default();
+    /// assert_eq!(ts.absorb(b"This is a test message"), Ok(()));
+    ///
+    /// let mut key_reader = ts.fork_finalize::<0x0b>().expect("must fork");
+    /// let mut nonce_reader = ts.fork_finalize::<0x0c>().expect("must fork");
+    ///
+    /// let mut key = [0u8; 32];
+    /// let mut nonce = [0u8; 12];
+    ///
+    /// key_reader.squeeze(&mut key);
+    /// nonce_reader.squeeze(&mut nonce);
+    /// ```
+    pub fn fork_finalize<const D: u8>(&self) -> Result<TurboShake256Reader, TurboShakeError> {
+        // See top of page 2 of https://ia.cr/2023/342
+        const { assert!(D >= 0x01 && D <= 0x7f) };
+
+        if branch_opt_util::unlikely(self.is_ready_to_squeeze == usize::MAX) {
+            return Err(TurboShakeError::DataAbsorptionPhaseAlreadyFinalized);
+        }
+
+        let mut state = self.state;
+        let mut offset = self.offset;
+        sponge::finalize::<{ Self::RATE_BYTES }, { D }>(&mut state, &mut offset);
+
+        Ok(TurboShake256Reader {
+            state,
+            squeezable: Self::RATE_BYTES,
+            position: 0,
+        })
+    }
+
     /// Squeezes arbitrary many output bytes from the TurboSHAKE256 sponge state.
     /// Only after the sponge state is finalized, it can be squeezed from.
     ///
@@ -131,7 +208,7 @@ impl TurboShake256 {
     /// # Outputs
     ///
     /// * `Result<(), TurboShakeError>`: `Ok(())` if the squeezing was successful.
-    /// Returns `Err(TurboShakeError::StillInDataAbsorptionPhase)` if the instance has not yet been finalized.
+    ///   Returns `Err(TurboShakeError::StillInDataAbsorptionPhase)` if the instance has not yet been finalized.
     ///
     /// # Example
     ///
@@ -153,6 +230,479 @@ impl TurboShake256 {
         }
 
         sponge::squeeze::<{ Self::RATE_BYTES }>(&mut self.state, &mut self.squeezable, out);
+        self.position += out.len() as u64;
         Ok(())
     }
+
+    /// Returns position of the finalized instance in TurboSHAKE256 output stream i.e. \# -of bytes squeezed or skipped so
+    /// far, which is 0, until it's finalized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::TurboShake256;
+    ///
+    /// let mut ts = TurboShake256::default();
+    /// assert_eq!(ts.finalize::<{TurboShake256::DEFAULT_DOMAIN_SEPARATOR}>(), Ok(()));
+    ///
+    /// let mut output = [0u8; 32];
+    /// assert_eq!(ts.squeeze(&mut output), Ok(()));
+    ///
+    /// assert_eq!(ts.position(), 32);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Skips `num_bytes` of TurboSHAKE256 output, as if they were squeezed and discarded, same as `TurboShake256Reader::skip`.
+    /// Only after the sponge state is finalized, output can be skipped.
+    ///
+    /// # Inputs
+    ///
+    /// * `num_bytes`: \# -of output bytes to be skipped.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), TurboShakeError>`: `Ok(())` if skipping was successful.
+    ///   Returns `Err(TurboShakeError::StillInDataAbsorptionPhase)` if the instance has not yet been finalized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::TurboShake256;
+    ///
+    /// let mut ts = TurboShake256::default();
+    /// assert_eq!(ts.finalize::<{TurboShake256::DEFAULT_DOMAIN_SEPARATOR}>(), Ok(()));
+    ///
+    /// // Reading bytes 4096..4128 of the output stream
+    /// let mut output = [0u8; 32];
+    /// assert_eq!(ts.skip(4096), Ok(()));
+    /// assert_eq!(ts.squeeze(&mut output), Ok(()));
+    /// ```
+    pub fn skip(&mut self, num_bytes: u64) -> Result<(), TurboShakeError> {
+        if branch_opt_util::unlikely(self.is_ready_to_squeeze != usize::MAX) {
+            return Err(TurboShakeError::StillInDataAbsorptionPhase);
+        }
+
+        sponge::skip::<{ Self::RATE_BYTES }>(&mut self.state, &mut self.squeezable, num_bytes);
+        self.position += num_bytes;
+        Ok(())
+    }
+
+    /// Records state of the finalized instance, at its current position in TurboSHAKE256 output stream, which can be restored
+    /// later, using `restore`, or turned into a `TurboShake256Reader`, starting at that position.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TurboShake256Checkpoint, TurboShakeError>`: Checkpoint of the instance, if it's finalized.
+    ///   Returns `Err(TurboShakeError::StillInDataAbsorptionPhase)` if the instance has not yet been finalized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::{TurboShake256, TurboShake256Reader};
+    ///
+    /// let mut ts = TurboShake256::default();
+    /// assert_eq!(ts.finalize::<{TurboShake256::DEFAULT_DOMAIN_SEPARATOR}>(), Ok(()));
+    /// assert_eq!(ts.skip(1024), Ok(()));
+    ///
+    /// let checkpoint = ts.checkpoint().expect("must take checkpoint");
+    ///
+    /// let reader = TurboShake256Reader::from(checkpoint);
+    /// assert_eq!(reader.position(), 1024);
+    /// ```
+    pub fn checkpoint(&self) -> Result<TurboShake256Checkpoint, TurboShakeError> {
+        if branch_opt_util::unlikely(self.is_ready_to_squeeze != usize::MAX) {
+            return Err(TurboShakeError::StillInDataAbsorptionPhase);
+        }
+
+        Ok(TurboShake256Checkpoint {
+            state: self.state,
+            squeezable: self.squeezable,
+            position: self.position,
+        })
+    }
+
+    /// Restores state recorded in `checkpoint`, taken from a finalized instance or a reader, bringing this instance to the
+    /// position in TurboSHAKE256 output stream, where checkpoint was taken. Irrespective of whether this instance was finalized
+    /// or not, it's finalized afterwards, so it can only be squeezed from.
+    ///
+    /// # Inputs
+    ///
+    /// * `checkpoint`: Checkpoint of a finalized instance or a reader, taken using `checkpoint`.
+    ///
+    /// # Returns
+    ///
+    /// Nothing, instance is at the position, where checkpoint was taken.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::TurboShake256;
+    ///
+    /// let mut ts = TurboShake256::default();
+    /// assert_eq!(ts.finalize::<{TurboShake256::DEFAULT_DOMAIN_SEPARATOR}>(), Ok(()));
+    ///
+    /// let mut output0 = [0u8; 32];
+    /// let mut output1 = [0u8; 32];
+    ///
+    /// let checkpoint = ts.checkpoint().expect("must take checkpoint");
+    /// assert_eq!(ts.squeeze(&mut output0), Ok(()));
+    ///
+    /// ts.restore(&checkpoint);
+    /// assert_eq!(ts.squeeze(&mut output1), Ok(()));
+    ///
+    /// assert_eq!(output0, output1);
+    /// ```
+    pub fn restore(&mut self, checkpoint: &TurboShake256Checkpoint) {
+        self.state = checkpoint.state;
+        self.is_ready_to_squeeze = usize::MAX;
+        self.squeezable = checkpoint.squeezable;
+        self.position = checkpoint.position;
+    }
+
+    /// Computes TurboSHAKE256 digest of a message, in one-shot, finalizing it using domain separator `D` and filling the
+    /// output slice. When output is at most 64 -bytes, last round of the permutation computes only lanes to be squeezed,
+    /// which makes it a bit faster than absorbing, finalizing and squeezing, using a `TurboShake256` object.
+    ///
+    /// # Inputs
+    ///
+    /// * `D`: A domain separator byte. Consider using `TurboShake256::DEFAULT_DOMAIN_SEPARATOR` if you don't need multiple instances of TurboSHAKE256.
+    /// * `msg`: An arbitrary length (including empty) slice of bytes to be absorbed.
+    /// * `out`: An arbitrary length (including empty) mutable slice of bytes to be filled with squeezed output.
+    ///
+    /// # Returns
+    ///
+    /// Nothing, output slice is filled with TurboSHAKE256 output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::TurboShake256;
+    ///
+    /// let mut md = [0u8; 64];
+    /// TurboShake256::oneshot::<{TurboShake256::DEFAULT_DOMAIN_SEPARATOR}>(b"This is a test message", &mut md);
+    /// ```
+    pub fn oneshot<const D: u8>(msg: &[u8], out: &mut [u8]) {
+        // See top of page 2 of https://ia.cr/2023/342
+        const { assert!(D >= 0x01 && D <= 0x7f) };
+
+        let mut state = [0u64; keccak::LANE_CNT];
+        let mut offset = 0;
+
+        sponge::absorb::<{ Self::RATE_BYTES }>(&mut state, &mut offset, msg);
+        sponge::finalize_and_squeeze_short::<{ Self::RATE_BYTES }, { D }>(&mut state, &mut offset, out);
+    }
+
+    /// Computes TurboSHAKE256 digests of two messages at once, finalizing both using domain separator `D` and filling both
+    /// output slices, which can be of different lengths. Full message blocks, common to both messages, are absorbed using
+    /// two-way Keccak-p\[1600, 12\] permutation, which is backed by WebAssembly SIMD128 instructions, when available, and by
+    /// portable `keccak::permute_many`, otherwise. It's most effective when both messages are of same length, such as leaves
+    /// of a K12-style tree hash.
+    ///
+    /// # Inputs
+    ///
+    /// * `D`: A domain separator byte. Consider using `TurboShake256::DEFAULT_DOMAIN_SEPARATOR` if you don't need multiple instances of TurboSHAKE256.
+    /// * `msgs`: Two arbitrary length (including empty) slices of bytes to be absorbed.
+    /// * `outs`: Two arbitrary length (including empty) mutable slices of bytes to be filled with squeezed output.
+    ///
+    /// # Returns
+    ///
+    /// Nothing, output slices are filled with TurboSHAKE256 output of corresponding message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::TurboShake256;
+    ///
+    /// let mut md0 = [0u8; 32];
+    /// let mut md1 = [0u8; 32];
+    ///
+    /// TurboShake256::oneshot_x2::<0x0b>([b"leaf 0", b"leaf 1"], [&mut md0, &mut md1]);
+    /// ```
+    pub fn oneshot_x2<const D: u8>(msgs: [&[u8]; 2], outs: [&mut [u8]; 2]) {
+        // See top of page 2 of https://ia.cr/2023/342
+        const { assert!(D >= 0x01 && D <= 0x7f) };
+
+        sponge::oneshot_x2::<{ Self::RATE_BYTES }, { D }>(msgs, outs);
+    }
+}
+
+/// Sponge state of TurboSHAKE256, recorded right after absorbing a prefix, say a key and a label, which is kept apart from
+/// `TurboShake256`, so that only those hashing many messages with a common prefix pay for it. It can create instances of
+/// `TurboShake256`, which have already absorbed the prefix, or reset an existing instance back to the prefix, neither
+/// allocating nor re-absorbing it.
+#[derive(Clone, Copy)]
+pub struct TurboShake256Prefix {
+    state: [u64; keccak::LANE_CNT],
+    offset: usize,
+}
+
+impl From<TurboShake256Prefix> for TurboShake256 {
+    /// Creates an instance of TurboSHAKE256, which has already absorbed the prefix.
+    fn from(prefix: TurboShake256Prefix) -> Self {
+        Self {
+            state: prefix.state,
+            offset: prefix.offset,
+            is_ready_to_squeeze: usize::MIN,
+            squeezable: 0,
+            position: 0,
+        }
+    }
+}
+
+impl TurboShake256Prefix {
+    /// Absorbs `prefix` into a fresh TurboSHAKE256 sponge state, recording the state right after absorbing it.
+    ///
+    /// # Inputs
+    ///
+    /// * `prefix`: An arbitrary length (including empty) slice of bytes to be absorbed.
+    ///
+    /// # Returns
+    ///
+    /// A `TurboShake256Prefix` object, recording the sponge state after absorbing `prefix`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::{TurboShake256, TurboShake256Prefix};
+    ///
+    /// let prefix = TurboShake256Prefix::new(b"key and label");
+    ///
+    /// let mut ts = TurboShake256::from(prefix);
+    /// assert_eq!(ts.absorb(b"message"), Ok(()));
+    /// ```
+    pub fn new(prefix: &[u8]) -> Self {
+        let mut state = [0u64; keccak::LANE_CNT];
+        let mut offset = 0;
+
+        sponge::absorb::<{ TurboShake256::RATE_BYTES }>(&mut state, &mut offset, prefix);
+        Self { state, offset }
+    }
+
+    /// Resets the TurboSHAKE256 instance `hasher`, irrespective of whether it's finalized or not, to the sponge state recorded
+    /// right after absorbing the prefix, so that it can be used for absorbing a new message.
+    ///
+    /// # Inputs
+    ///
+    /// * `hasher`: A `TurboShake256` object, to be reset.
+    ///
+    /// # Returns
+    ///
+    /// Nothing, `hasher` is ready for absorbing a new message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::{TurboShake256, TurboShake256Prefix};
+    ///
+    /// let mut md = [0u8; 32];
+    /// let prefix = TurboShake256Prefix::new(b"key and label");
+    /// let mut ts = TurboShake256::from(prefix);
+    ///
+    /// for msg in [b"message 0", b"message 1"] {
+    ///     prefix.reset_to_prefix(&mut ts);
+    ///
+    ///     assert_eq!(ts.absorb(msg), Ok(()));
+    ///     assert_eq!(ts.finalize::<{TurboShake256::DEFAULT_DOMAIN_SEPARATOR}>(), Ok(()));
+    ///     assert_eq!(ts.squeeze(&mut md), Ok(()));
+    /// }
+    /// ```
+    pub fn reset_to_prefix(&self, hasher: &mut TurboShake256) {
+        *hasher = TurboShake256::from(*self);
+    }
+}
+
+/// Reader of TurboSHAKE256 Extendable Output Function (XOF) output, holding a finalized sponge state,
+/// which is obtained by forking a `TurboShake256` instance, using `fork_finalize`. It keeps track of
+/// its position in the output stream, which can be moved forward, by skipping bytes.
+#[derive(Clone)]
+pub struct TurboShake256Reader {
+    state: [u64; keccak::LANE_CNT],
+    squeezable: usize,
+    position: u64,
+}
+
+/// Checkpoint of a `TurboShake256Reader` or a finalized `TurboShake256`, recording its state at some position in the output
+/// stream, which can be restored later, or a new reader can be created from it, starting at that position.
+#[derive(Clone, Copy)]
+pub struct TurboShake256Checkpoint {
+    state: [u64; keccak::LANE_CNT],
+    squeezable: usize,
+    position: u64,
+}
+
+impl From<TurboShake256Checkpoint> for TurboShake256Reader {
+    /// Creates a reader of TurboSHAKE256 output, starting at position recorded in the checkpoint.
+    fn from(checkpoint: TurboShake256Checkpoint) -> Self {
+        Self {
+            state: checkpoint.state,
+            squeezable: checkpoint.squeezable,
+            position: checkpoint.position,
+        }
+    }
+}
+
+impl TurboShake256Reader {
+    /// Squeezes arbitrary many output bytes from the finalized TurboSHAKE256 sponge state.
+    ///
+    /// # Inputs
+    ///
+    /// * `out`: An arbitrary length (including empty) mutable slice of bytes to be filled with squeezed output.
+    ///
+    /// # Returns
+    ///
+    /// Nothing, output slice is filled with next `out.len()` -bytes of TurboSHAKE256 output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::TurboShake256;
+    ///
+    /// let mut ts = TurboShake256::default();
+    /// assert_eq!(ts.absorb(b"This is a test message"), Ok(()));
+    ///
+    /// let mut reader = ts.fork_finalize::<{TurboShake256::DEFAULT_DOMAIN_SEPARATOR}>().expect("must fork");
+    ///
+    /// let mut output = [0u8; 32];
+    /// reader.squeeze(&mut output);
+    /// ```
+    pub fn squeeze(&mut self, out: &mut [u8]) {
+        sponge::squeeze::<{ TurboShake256::RATE_BYTES }>(&mut self.state, &mut self.squeezable, out);
+        self.position += out.len() as u64;
+    }
+
+    /// Returns position of the reader in TurboSHAKE256 output stream i.e. \# -of bytes squeezed or skipped so far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::TurboShake256;
+    ///
+    /// let ts = TurboShake256::default();
+    /// let mut reader = ts.fork_finalize::<{TurboShake256::DEFAULT_DOMAIN_SEPARATOR}>().expect("must fork");
+    ///
+    /// let mut output = [0u8; 32];
+    /// reader.squeeze(&mut output);
+    ///
+    /// assert_eq!(reader.position(), 32);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Skips `num_bytes` of TurboSHAKE256 output, as if they were squeezed and discarded. Instead of copying
+    /// skipped bytes out, it only permutes the sponge state, once for each block being skipped.
+    ///
+    /// # Inputs
+    ///
+    /// * `num_bytes`: \# -of output bytes to be skipped.
+    ///
+    /// # Returns
+    ///
+    /// Nothing, reader moves forward by `num_bytes` in the output stream.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::TurboShake256;
+    ///
+    /// let ts = TurboShake256::default();
+    /// let mut reader = ts.fork_finalize::<{TurboShake256::DEFAULT_DOMAIN_SEPARATOR}>().expect("must fork");
+    ///
+    /// // Reading bytes 4096..4128 of the output stream
+    /// let mut output = [0u8; 32];
+    /// reader.skip(4096);
+    /// reader.squeeze(&mut output);
+    /// ```
+    pub fn skip(&mut self, num_bytes: u64) {
+        sponge::skip::<{ TurboShake256::RATE_BYTES }>(&mut self.state, &mut self.squeezable, num_bytes);
+        self.position += num_bytes;
+    }
+
+    /// Records state of the reader, at its current position in TurboSHAKE256 output stream, which can be restored later,
+    /// using `restore`. Checkpoint can also be turned into a new reader, so that parallel consumers can be forked off
+    /// known positions of the output stream.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::{TurboShake256, TurboShake256Reader};
+    ///
+    /// let ts = TurboShake256::default();
+    /// let mut reader = ts.fork_finalize::<{TurboShake256::DEFAULT_DOMAIN_SEPARATOR}>().expect("must fork");
+    ///
+    /// reader.skip(1024);
+    /// let checkpoint = reader.checkpoint();
+    ///
+    /// let mut another_reader = TurboShake256Reader::from(checkpoint);
+    /// assert_eq!(another_reader.position(), 1024);
+    /// ```
+    pub fn checkpoint(&self) -> TurboShake256Checkpoint {
+        TurboShake256Checkpoint {
+            state: self.state,
+            squeezable: self.squeezable,
+            position: self.position,
+        }
+    }
+
+    /// Restores state of the reader, recorded in `checkpoint`, bringing it back to the position in TurboSHAKE256 output
+    /// stream, where checkpoint was taken.
+    ///
+    /// # Inputs
+    ///
+    /// * `checkpoint`: Checkpoint of a reader, taken using `checkpoint`.
+    ///
+    /// # Returns
+    ///
+    /// Nothing, reader is at the position, where checkpoint was taken.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use turboshake::TurboShake256;
+    ///
+    /// let ts = TurboShake256::default();
+    /// let mut reader = ts.fork_finalize::<{TurboShake256::DEFAULT_DOMAIN_SEPARATOR}>().expect("must fork");
+    ///
+    /// let mut output0 = [0u8; 32];
+    /// let mut output1 = [0u8; 32];
+    ///
+    /// let checkpoint = reader.checkpoint();
+    /// reader.squeeze(&mut output0);
+    ///
+    /// reader.restore(&checkpoint);
+    /// reader.squeeze(&mut output1);
+    ///
+    /// assert_eq!(output0, output1);
+    /// ```
+    pub fn restore(&mut self, checkpoint: &TurboShake256Checkpoint) {
+        self.state = checkpoint.state;
+        self.squeezable = checkpoint.squeezable;
+        self.position = checkpoint.position;
+    }
+}
+
+/// Compile-time evaluable one-shot TurboSHAKE256, which absorbs `msg`, finalizes using
+/// `TurboShake256::DEFAULT_DOMAIN_SEPARATOR` and squeezes `N` -bytes output. Handy for deriving
+/// fixed public parameters or domain-separated protocol constants from labels, at compile-time.
+/// It can be called at runtime too, though `TurboShake256` is much faster for that.
+///
+/// # Inputs
+///
+/// * `msg`: An arbitrary length (including empty) slice of bytes to be absorbed.
+///
+/// # Returns
+///
+/// * `[u8; N]`: First `N` -bytes of TurboSHAKE256 output.
+///
+/// # Example
+///
+/// ```
+/// use turboshake::turboshake256_const;
+///
+/// const LABEL_DIGEST: [u8; 32] = turboshake256_const(b"label");
+/// ```
+pub const fn turboshake256_const<const N: usize>(msg: &[u8]) -> [u8; N] {
+    sponge::oneshot::<{ TurboShake256::RATE_BYTES }, { TurboShake256::DEFAULT_DOMAIN_SEPARATOR }, N>(msg)
 }